@@ -5,7 +5,11 @@ use {
     crate::{schema::setup, task::Task, types::DirectoryId, Result},
     async_trait::async_trait,
     gluesql::{
-        core::ast_builder::Build,
+        core::{
+            ast::Statement,
+            ast_builder::Build,
+            store::{GStore, GStoreMut},
+        },
         gluesql_git_storage::{GitStorage, StorageType},
         prelude::{CsvStorage, FileStorage, Glue, JsonStorage, MemoryStorage, Payload},
     },
@@ -26,6 +30,23 @@ pub enum Storage {
     File(Glue<FileStorage>),
     Git(Glue<GitStorage>),
     Mongo(Glue<MongoStorage>),
+    Custom(Box<dyn Backend>),
+}
+
+/// Type-erased GlueSQL executor, implemented for every `Glue<T>`.
+///
+/// Downstream crates can plug their own store into glues through
+/// [`Db::custom`] without adding a variant to [`Storage`].
+#[async_trait(?Send)]
+pub trait Backend {
+    async fn execute_stmt(&mut self, statement: &Statement) -> gluesql::prelude::Result<Payload>;
+}
+
+#[async_trait(?Send)]
+impl<T: GStore + GStoreMut> Backend for Glue<T> {
+    async fn execute_stmt(&mut self, statement: &Statement) -> gluesql::prelude::Result<Payload> {
+        Glue::execute_stmt(self, statement).await
+    }
 }
 
 impl Db {
@@ -113,6 +134,21 @@ impl Db {
         })
     }
 
+    pub async fn custom<T>(task_tx: Sender<Task>, storage: T) -> Result<Self>
+    where
+        T: GStore + GStoreMut + 'static,
+    {
+        let mut storage = Storage::Custom(Box::new(Glue::new(storage)));
+
+        let root_id = setup(&mut storage).await?;
+
+        Ok(Self {
+            storage,
+            root_id,
+            task_tx,
+        })
+    }
+
     pub async fn pull(&mut self) -> Result<()> {
         if let Storage::Git(glue) = &mut self.storage {
             glue.storage.pull()?;
@@ -163,6 +199,7 @@ where
             Storage::File(glue) => glue.execute_stmt(&statement).await,
             Storage::Git(glue) => glue.execute_stmt(&statement).await,
             Storage::Mongo(glue) => glue.execute_stmt(&statement).await,
+            Storage::Custom(backend) => backend.execute_stmt(&statement).await,
         }
         .map_err(Into::into)
     }
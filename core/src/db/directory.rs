@@ -1,6 +1,10 @@
 use {
     super::{Db, Execute},
-    crate::{data::Directory, types::DirectoryId, Result},
+    crate::{
+        data::Directory,
+        types::{DirectoryId, NoteId},
+        Result,
+    },
     async_recursion::async_recursion,
     gluesql::core::ast_builder::{col, function::now, table, text, uuid},
     std::ops::Deref,
@@ -69,8 +73,16 @@ impl Db {
         self.sync().map(|()| directory)
     }
 
+    /// Returns the notes removed along with it, those of its subdirectories included.
     #[async_recursion(?Send)]
-    pub async fn remove_directory(&mut self, directory_id: DirectoryId) -> Result<()> {
+    pub async fn remove_directory(&mut self, directory_id: DirectoryId) -> Result<Vec<NoteId>> {
+        let mut note_ids: Vec<NoteId> = self
+            .fetch_notes(directory_id.clone())
+            .await?
+            .into_iter()
+            .map(|note| note.id)
+            .collect();
+
         table("Note")
             .delete()
             .filter(col("directory_id").eq(uuid(directory_id.clone())))
@@ -79,7 +91,7 @@ impl Db {
 
         let directories = self.fetch_directories(directory_id.clone()).await?;
         for directory in directories {
-            self.remove_directory(directory.id).await?;
+            note_ids.extend(self.remove_directory(directory.id).await?);
         }
 
         table("Directory")
@@ -88,7 +100,7 @@ impl Db {
             .execute(&mut self.storage)
             .await?;
 
-        self.sync()
+        self.sync().map(|()| note_ids)
     }
 
     pub async fn move_directory(
@@ -5,6 +5,9 @@ pub enum Error {
     #[error("gluesql: {0}")]
     GlueSql(#[from] gluesql::prelude::Error),
 
+    #[error("not a number key: {0}")]
+    InvalidNumKey(char),
+
     #[error("wip: {0}")]
     Wip(String),
 }
@@ -2,6 +2,7 @@ use {
     crate::{
         data::{Directory, Note},
         types::{DirectoryId, NoteId},
+        Error, Result,
     },
     strum_macros::Display,
 };
@@ -64,49 +65,46 @@ pub enum NotebookEvent {
     EditNote,
     ViewNote,
 
-    UpdateNoteContent { note_id: NoteId, content: String },
+    UpdateNoteContent {
+        note_id: NoteId,
+        content: String,
+    },
+
+    /// The pattern typed for `/` or `?`, resolved to the last one when left empty.
+    Search(String),
+    RunCommand(String),
+    /// Sent back for `:q` once the tab is found to have nothing to lose.
+    CloseTab,
 
     CloseEntryDialog,
+
+    /// The frontend holds what the tabs have not saved yet, so it passes their contents
+    /// in to be written before the storage is closed; the notebook stays open on failure.
+    CloseNotebook {
+        unsaved: Vec<(NoteId, String)>,
+    },
 }
 
-#[derive(Clone, Copy, Debug, Display)]
+/// Characters already carry shift, so `J` arrives as `Char('J')`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Display)]
 pub enum KeyEvent {
-    A,
-    B,
-    C,
-    D,
-    E,
-    G,
-    H,
-    I,
-    J,
-    K,
-    L,
-    M,
-    N,
-    O,
-    P,
-    S,
-    T,
-    U,
-    V,
-    W,
-    X,
-    Y,
-    CapA,
-    CapG,
-    CapI,
-    CapO,
-    CapS,
-    CtrlH,
-    CtrlR,
-    DollarSign,
-    Caret,
-    Num(NumKey),
+    #[strum(to_string = "{0}")]
+    Char(char),
+    #[strum(to_string = "Ctrl+{0}")]
+    Ctrl(char),
+    #[strum(to_string = "Alt+{0}")]
+    Alt(char),
     Left,
     Right,
     Up,
     Down,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Tab,
+    Backspace,
+    Delete,
     Enter,
     Esc,
 }
@@ -143,9 +141,25 @@ impl From<KeyEvent> for Event {
     }
 }
 
-impl From<NumKey> for KeyEvent {
-    fn from(num_key: NumKey) -> Self {
-        KeyEvent::Num(num_key)
+impl TryFrom<char> for NumKey {
+    type Error = Error;
+
+    fn try_from(c: char) -> Result<Self> {
+        let num_key = match c {
+            '1' => NumKey::One,
+            '2' => NumKey::Two,
+            '3' => NumKey::Three,
+            '4' => NumKey::Four,
+            '5' => NumKey::Five,
+            '6' => NumKey::Six,
+            '7' => NumKey::Seven,
+            '8' => NumKey::Eight,
+            '9' => NumKey::Nine,
+            '0' => NumKey::Zero,
+            _ => return Err(Error::InvalidNumKey(c)),
+        };
+
+        Ok(num_key)
     }
 }
 
@@ -1,10 +1,11 @@
 use {
     crate::{
         db::Db,
-        state::{EntryState, State},
+        state::{EntryState, NotebookState, State},
         task::{handle_tasks, Task},
-        Event, Result, Transition,
+        EntryTransition, Event, Result, Transition,
     },
+    gluesql::core::store::{GStore, GStoreMut},
     std::{
         collections::VecDeque,
         sync::{
@@ -40,6 +41,28 @@ impl Glues {
     }
 
     pub async fn dispatch(&mut self, event: Event) -> Result<Transition> {
-        State::consume(self, event).await
+        let label = event.to_string();
+        let result = State::consume(self, event).await;
+
+        match &result {
+            Ok(transition) => log::debug!("{label} => {transition}"),
+            Err(error) => log::warn!("{label} => {error}"),
+        }
+
+        result
+    }
+
+    /// Opens a notebook backed by a storage that glues does not ship with.
+    ///
+    /// Built-in backends are opened through [`crate::EntryEvent`]; a store
+    /// can't travel inside an event, so custom ones come in through here.
+    pub async fn open_custom<T>(&mut self, storage: T) -> Result<Transition>
+    where
+        T: GStore + GStoreMut + 'static,
+    {
+        self.db = Db::custom(self.task_tx.clone(), storage).await.map(Some)?;
+        self.state = NotebookState::new(self).await?.into();
+
+        Ok(EntryTransition::OpenNotebook.into())
     }
 }
@@ -10,6 +10,7 @@ pub mod state;
 pub mod transition;
 pub mod types;
 
+pub use db::{Backend, Db, Storage};
 pub use error::Error;
 pub use event::{EntryEvent, Event, KeyEvent, NotebookEvent, NumKey};
 pub use glues::Glues;
@@ -5,16 +5,18 @@ mod inner_state;
 use {
     crate::{
         data::{Directory, Note},
-        state::GetInner,
+        state::{EntryState, GetInner},
         types::DirectoryId,
-        Error, Event, Glues, NotebookTransition, Result,
+        Error, Event, Glues, NotebookEvent, NotebookTransition, Result,
     },
     consume::{directory, note, tabs, traverse},
 };
 
 pub use inner_state::{
+    Address, AddressBase, CaseKind, CharFind, FindAction, FindKind,
     InnerState::{self, *},
-    VimNormalState, VimVisualState,
+    LineRange, Macros, Motion, ObjectKind, ObjectScope, Repeat, SearchDirection, Substitution,
+    TextObject, VimNormalState, VimVisualState, VisualKind,
 };
 
 pub use directory_item::{DirectoryItem, DirectoryItemChildren, TreeItem};
@@ -24,6 +26,15 @@ pub struct NotebookState {
     pub selected: SelectedItem,
     pub tabs: Vec<Note>,
     pub tab_index: Option<usize>,
+    /// A confirmed search is highlighted, so `n` and `N` move between its matches.
+    pub searching: bool,
+    /// The last `f`, `t`, `F` or `T`, repeated by `;` and `,`.
+    pub last_find: Option<CharFind>,
+    /// The last change, replayed by `.`.
+    pub repeat: Repeat,
+    pub macros: Macros,
+    /// Whether visual mode selects characters, whole lines or a block.
+    pub visual_kind: VisualKind,
 
     pub inner_state: InnerState,
 }
@@ -65,6 +76,11 @@ impl NotebookState {
             selected,
             tabs: Vec::new(),
             tab_index: None,
+            searching: false,
+            last_find: None,
+            repeat: Repeat::default(),
+            macros: Macros::default(),
+            visual_kind: VisualKind::Charwise,
         })
     }
 
@@ -79,7 +95,7 @@ impl NotebookState {
     }
 
     pub fn describe(&self) -> Result<String> {
-        Ok(match &self.inner_state {
+        let description = match &self.inner_state {
             NoteMoreActions => "Note actions dialog".to_owned(),
             DirectoryMoreActions => "Directory actions dialog".to_owned(),
             NoteSelected => {
@@ -110,11 +126,35 @@ impl NotebookState {
 
                 format!("Note '{name}' normal mode, steps: '{n}'")
             }
-            EditingNormalMode(VimNormalState::Gateway) => {
+            EditingNormalMode(VimNormalState::Gateway(_)) => {
                 let name = &self.get_selected_note()?.name;
 
                 format!("Note '{name}' normal mode - gateway")
             }
+            EditingNormalMode(VimNormalState::Indent(n) | VimNormalState::Outdent(n)) => {
+                let name = &self.get_selected_note()?.name;
+                let key = match self.inner_state {
+                    EditingNormalMode(VimNormalState::Indent(_)) => '>',
+                    _ => '<',
+                };
+                let n = if *n >= 2 {
+                    format!("{n}")
+                } else {
+                    "".to_owned()
+                };
+
+                format!("Note '{name}' normal mode - shift '{n}{key}'")
+            }
+            EditingNormalMode(VimNormalState::Case(_, kind)) => {
+                let name = &self.get_selected_note()?.name;
+
+                format!("Note '{name}' normal mode - {}", case_name(*kind))
+            }
+            EditingNormalMode(VimNormalState::ReplaceChar(_)) => {
+                let name = &self.get_selected_note()?.name;
+
+                format!("Note '{name}' normal mode - replace character")
+            }
             EditingNormalMode(VimNormalState::Yank(n)) => {
                 let name = &self.get_selected_note()?.name;
 
@@ -165,16 +205,6 @@ impl NotebookState {
 
                 format!("Note '{name}' normal mode - delete '{n1}d{n2}'")
             }
-            EditingNormalMode(VimNormalState::DeleteInside(n)) => {
-                let name = &self.get_selected_note()?.name;
-                let n = if *n >= 2 {
-                    format!("{n}")
-                } else {
-                    "".to_owned()
-                };
-
-                format!("Note '{name}' normal mode - delete inside {n}di")
-            }
             EditingNormalMode(VimNormalState::Change(n)) => {
                 let name = &self.get_selected_note()?.name;
 
@@ -200,36 +230,133 @@ impl NotebookState {
 
                 format!("Note '{name}' normal mode - change '{n1}c{n2}'")
             }
-            EditingNormalMode(VimNormalState::ChangeInside(n)) => {
+            EditingVisualMode(VimVisualState::Idle) => {
                 let name = &self.get_selected_note()?.name;
-                let n = if *n >= 2 {
-                    format!("{n}")
-                } else {
-                    "".to_owned()
+
+                format!("Note '{name}' {} mode", self.visual_kind.name())
+            }
+            EditingVisualMode(VimVisualState::Numbering(n)) => {
+                let name = &self.get_selected_note()?.name;
+
+                format!(
+                    "Note '{name}' {} mode, input: '{n}'",
+                    self.visual_kind.name()
+                )
+            }
+            EditingVisualMode(VimVisualState::Gateway) => {
+                let name = &self.get_selected_note()?.name;
+
+                format!("Note '{name}' {} mode - gateway", self.visual_kind.name())
+            }
+            EditingNormalMode(VimNormalState::Search(direction)) => {
+                let name = &self.get_selected_note()?.name;
+                let direction = match direction {
+                    SearchDirection::Forward => "forward",
+                    SearchDirection::Backward => "backward",
                 };
 
-                format!("Note '{name}' normal mode - change inside {n}ci")
+                format!("Note '{name}' normal mode - search {direction}")
             }
-            EditingVisualMode(VimVisualState::Idle) => {
+            EditingNormalMode(VimNormalState::Command) => {
                 let name = &self.get_selected_note()?.name;
 
-                format!("Note '{name}' visual mode")
+                format!("Note '{name}' normal mode - command")
             }
-            EditingVisualMode(VimVisualState::Numbering(n)) => {
+            EditingNormalMode(VimNormalState::Register) => {
                 let name = &self.get_selected_note()?.name;
 
-                format!("Note '{name}' visual mode, input: '{n}'")
+                format!("Note '{name}' normal mode - register")
             }
-            EditingVisualMode(VimVisualState::Gateway) => {
+            EditingVisualMode(VimVisualState::Register) => {
+                let name = &self.get_selected_note()?.name;
+
+                format!("Note '{name}' {} mode - register", self.visual_kind.name())
+            }
+            EditingNormalMode(VimNormalState::Find(_, _, action)) => {
+                let name = &self.get_selected_note()?.name;
+                let action = match action {
+                    FindAction::Move => "find".to_owned(),
+                    FindAction::Delete => "delete to character".to_owned(),
+                    FindAction::Change => "change to character".to_owned(),
+                    FindAction::Yank => "yank to character".to_owned(),
+                    FindAction::Case(kind) => format!("{} to character", case_name(*kind)),
+                };
+
+                format!("Note '{name}' normal mode - {action}")
+            }
+            EditingNormalMode(VimNormalState::TextObject(_, scope, action)) => {
+                let name = &self.get_selected_note()?.name;
+                let action = match action {
+                    FindAction::Move => "select",
+                    FindAction::Delete => "delete",
+                    FindAction::Change => "change",
+                    FindAction::Yank => "yank",
+                    FindAction::Case(kind) => case_name(*kind),
+                };
+                let scope = match scope {
+                    ObjectScope::Inner => "inside",
+                    ObjectScope::Around => "around",
+                };
+
+                format!("Note '{name}' normal mode - {action} {scope}")
+            }
+            EditingVisualMode(VimVisualState::TextObject(_, scope)) => {
+                let name = &self.get_selected_note()?.name;
+                let scope = match scope {
+                    ObjectScope::Inner => "inside",
+                    ObjectScope::Around => "around",
+                };
+
+                format!(
+                    "Note '{name}' {} mode - select {scope}",
+                    self.visual_kind.name()
+                )
+            }
+            EditingVisualMode(VimVisualState::Find(..)) => {
                 let name = &self.get_selected_note()?.name;
 
-                format!("Note '{name}' visual mode - gateway")
+                format!("Note '{name}' {} mode - find", self.visual_kind.name())
             }
             EditingInsertMode => {
                 let name = &self.get_selected_note()?.name;
 
                 format!("Note '{name}' insert mode")
             }
+            EditingReplaceMode => {
+                let name = &self.get_selected_note()?.name;
+
+                format!("Note '{name}' replace mode")
+            }
+            EditingNormalMode(VimNormalState::RecordMacro) => {
+                let name = &self.get_selected_note()?.name;
+
+                format!("Note '{name}' normal mode - record macro")
+            }
+            EditingNormalMode(VimNormalState::PlayMacro(n)) => {
+                let name = &self.get_selected_note()?.name;
+                let n = if *n >= 2 {
+                    format!("{n}")
+                } else {
+                    "".to_owned()
+                };
+
+                format!("Note '{name}' normal mode - run macro '{n}@'")
+            }
+            EditingNormalMode(VimNormalState::Mark) => {
+                let name = &self.get_selected_note()?.name;
+
+                format!("Note '{name}' normal mode - set mark")
+            }
+            EditingNormalMode(VimNormalState::JumpToMark(_)) => {
+                let name = &self.get_selected_note()?.name;
+
+                format!("Note '{name}' normal mode - jump to mark")
+            }
+        };
+
+        Ok(match self.macros.recording() {
+            Some(register) => format!("{description}, recording @{register}"),
+            None => description,
         })
     }
 
@@ -242,6 +369,7 @@ impl NotebookState {
                     "[j|k] Down | Up".to_owned(),
                     "[1-9] Set steps".to_owned(),
                     "[m] More actions".to_owned(),
+                    "[q] Close notebook".to_owned(),
                     "[Esc] Quit".to_owned(),
                 ]
             }
@@ -252,6 +380,7 @@ impl NotebookState {
                     "[j|k] Down | Up".to_owned(),
                     "[1-9] Set steps".to_owned(),
                     "[m] More actions".to_owned(),
+                    "[q] Close notebook".to_owned(),
                     "[Esc] Quit".to_owned(),
                 ]
             }
@@ -268,11 +397,35 @@ impl NotebookState {
                     h j k l w e b [1-9] o O 0 $
                     a, A, I, G, g, s, S, x, ^, y, d, u, Ctrl+r
                 */
+                if let Some(register) = self.macros.recording() {
+                    return vec![
+                        format!("[q] Stop recording @{register}"),
+                        "[/|?|:] Search | Command".to_owned(),
+                        "[i] Insert".to_owned(),
+                        "[v] Visual".to_owned(),
+                        "[Ctrl+h] Show Vim keymap".to_owned(),
+                    ];
+                }
+
+                if self.searching {
+                    return vec![
+                        "[n|N] Next | Prev match".to_owned(),
+                        "[/|?] Search".to_owned(),
+                        "[Space] Toggle | Tabs".to_owned(),
+                        "[i] Insert".to_owned(),
+                        "[v] Visual".to_owned(),
+                        "[Ctrl+h] Show Vim keymap".to_owned(),
+                        "[Esc] Clear search".to_owned(),
+                    ];
+                }
+
                 vec![
                     "[n] Browse notes".to_owned(),
-                    "[t] Toggle | Tabs".to_owned(),
+                    "[/|?|:] Search | Command".to_owned(),
+                    "[Space] Toggle | Tabs".to_owned(),
                     "[i] Insert".to_owned(),
                     "[v] Visual".to_owned(),
+                    "[Ctrl+s] Save".to_owned(),
                     "[Ctrl+h] Show Vim keymap".to_owned(),
                     "[Esc] Quit".to_owned(),
                 ]
@@ -295,15 +448,44 @@ impl NotebookState {
                     "[Esc] Cancel".to_owned(),
                 ]
             }
-            EditingNormalMode(VimNormalState::Gateway) => {
+            EditingNormalMode(VimNormalState::Gateway(_)) => {
                 vec![
                     "[g] Move cursor to top".to_owned(),
+                    "[~|u|U] Toggle case | Lowercase | Uppercase".to_owned(),
+                    "[Esc] Cancel".to_owned(),
+                ]
+            }
+            EditingNormalMode(VimNormalState::Indent(n)) => {
+                vec![format!("[>] Indent {n} lines"), "[Esc] Cancel".to_owned()]
+            }
+            EditingNormalMode(VimNormalState::Outdent(n)) => {
+                vec![format!("[<] Outdent {n} lines"), "[Esc] Cancel".to_owned()]
+            }
+            EditingNormalMode(VimNormalState::Case(n, kind)) => {
+                vec![
+                    format!("[{}] Change {n} lines", kind.key()),
+                    "[w|e|b|0|$|}|%] Change to the motion".to_owned(),
+                    "[i|a] Inside | Around text object".to_owned(),
+                    "[f|t|F|T] Change to a character".to_owned(),
+                    "[Esc] Cancel".to_owned(),
+                ]
+            }
+            EditingNormalMode(VimNormalState::ReplaceChar(n)) => {
+                let chars = if *n >= 2 {
+                    format!("{n} characters")
+                } else {
+                    "the character".to_owned()
+                };
+
+                vec![
+                    format!("[any key] Replace {chars} under the cursor"),
                     "[Esc] Cancel".to_owned(),
                 ]
             }
             EditingNormalMode(VimNormalState::Yank(n)) => {
                 vec![
                     format!("[y] Yank {n} lines"),
+                    "[i|a] Inside | Around text object".to_owned(),
                     "[1-9] Append steps".to_owned(),
                     "[Esc] Cancel".to_owned(),
                 ]
@@ -315,13 +497,14 @@ impl NotebookState {
                     } else {
                         format!("[y] Yank {n1}*{n2} lines")
                     },
+                    "[i|a] Inside | Around text object".to_owned(),
                     "[0-9] Append steps".to_owned(),
                     "[Esc] Cancel".to_owned(),
                 ]
             }
             EditingNormalMode(VimNormalState::Delete(n)) => {
                 vec![
-                    format!("[i] Inside mode"),
+                    "[i|a] Inside | Around text object".to_owned(),
                     format!("[d] Delete {n} lines"),
                     "[1-9] Append steps".to_owned(),
                     "[Ctrl+h] Show Vim keymap".to_owned(),
@@ -335,25 +518,15 @@ impl NotebookState {
                     } else {
                         format!("[d] Delete {n1}*{n2} lines")
                     },
-                    "[i] Inside mode".to_owned(),
+                    "[i|a] Inside | Around text object".to_owned(),
                     "[0-9] Append steps".to_owned(),
                     "[Ctrl+h] Show Vim keymap".to_owned(),
                     "[Esc] Cancel".to_owned(),
                 ]
             }
-            EditingNormalMode(VimNormalState::DeleteInside(n)) => {
-                vec![
-                    if *n == 1 {
-                        "[w] Delete the current word".to_owned()
-                    } else {
-                        format!("[w] Delete {n} words from cursor")
-                    },
-                    "[Esc] Cancel".to_owned(),
-                ]
-            }
             EditingNormalMode(VimNormalState::Change(n)) => {
                 vec![
-                    "[i] Inside mode".to_owned(),
+                    "[i|a] Inside | Around text object".to_owned(),
                     format!("[c] Delete {n} lines and insert mode"),
                     "[Ctrl+h] Show Vim keymap".to_owned(),
                     "[Esc] Cancel".to_owned(),
@@ -366,24 +539,109 @@ impl NotebookState {
                     } else {
                         format!("[c] Delete {n1}*{n2} lines and insert mode")
                     },
-                    "[i] Inside mode".to_owned(),
+                    "[i|a] Inside | Around text object".to_owned(),
                     "[0-9] Append steps".to_owned(),
                     "[Ctrl+h] Show Vim keymap".to_owned(),
                     "[Esc] Cancel".to_owned(),
                 ]
             }
-            EditingNormalMode(VimNormalState::ChangeInside(n)) => {
+            EditingNormalMode(VimNormalState::TextObject(..))
+            | EditingVisualMode(VimVisualState::TextObject(..)) => {
                 vec![
-                    if *n == 1 {
-                        "[w] Delete the current word and insert mode".to_owned()
-                    } else {
-                        format!("[w] Delete {n} words from cursor and insert mode")
-                    },
+                    "[w|W] Word | WORD".to_owned(),
+                    "[s|p] Sentence | Paragraph".to_owned(),
+                    "[\"|'|`] Quotes".to_owned(),
+                    "[(|[|{|<] Brackets".to_owned(),
+                    "[Esc] Cancel".to_owned(),
+                ]
+            }
+            EditingNormalMode(VimNormalState::Search(_)) => {
+                vec!["[Enter] Search".to_owned(), "[Esc] Cancel".to_owned()]
+            }
+            EditingNormalMode(VimNormalState::Command) => {
+                vec!["[Enter] Run".to_owned(), "[Esc] Cancel".to_owned()]
+            }
+            EditingNormalMode(VimNormalState::Register)
+            | EditingVisualMode(VimVisualState::Register) => {
+                vec![
+                    "[a-z] Named register".to_owned(),
+                    "[A-Z] Append to named register".to_owned(),
+                    "[0-9] Yank and delete history".to_owned(),
+                    "[+|*] Clipboard".to_owned(),
+                    "[_] Black hole".to_owned(),
+                    "[Esc] Cancel".to_owned(),
+                ]
+            }
+            EditingNormalMode(VimNormalState::Find(n, kind, _))
+            | EditingVisualMode(VimVisualState::Find(n, kind)) => {
+                let target = match kind {
+                    FindKind::Find => "to the next",
+                    FindKind::Till => "before the next",
+                    FindKind::FindBack => "to the previous",
+                    FindKind::TillBack => "after the previous",
+                };
+                let times = if *n >= 2 {
+                    format!(" {n} times")
+                } else {
+                    "".to_owned()
+                };
+
+                vec![
+                    format!("[any key] Move {target} match{times}"),
+                    "[Esc] Cancel".to_owned(),
+                ]
+            }
+            EditingNormalMode(VimNormalState::RecordMacro) => {
+                vec![
+                    "[a-z|0-9] Record into the register".to_owned(),
+                    "[A-Z] Append to the register".to_owned(),
+                    "[Esc] Cancel".to_owned(),
+                ]
+            }
+            EditingNormalMode(VimNormalState::PlayMacro(n)) => {
+                let times = if *n >= 2 {
+                    format!(" {n} times")
+                } else {
+                    "".to_owned()
+                };
+
+                vec![
+                    format!("[a-z|0-9] Run the register{times}"),
+                    format!("[@] Run the last macro{times}"),
+                    "[Esc] Cancel".to_owned(),
+                ]
+            }
+            EditingNormalMode(VimNormalState::Mark) => {
+                vec![
+                    "[a-z] Mark the cursor in this note".to_owned(),
+                    "[A-Z] Mark the cursor across notes".to_owned(),
+                    "[Esc] Cancel".to_owned(),
+                ]
+            }
+            EditingNormalMode(VimNormalState::JumpToMark(linewise)) => {
+                let target = if *linewise {
+                    "the line of the mark"
+                } else {
+                    "the mark"
+                };
+
+                vec![
+                    format!("[a-z|A-Z] Jump to {target}"),
                     "[Esc] Cancel".to_owned(),
                 ]
             }
             EditingVisualMode(VimVisualState::Idle) => {
                 // more in the keymap
+                if self.visual_kind == VisualKind::Blockwise {
+                    return vec![
+                        "[h|j|k|l] Move cursor".to_owned(),
+                        "[I|A] Insert | Append on each line".to_owned(),
+                        "[y|d|c] Yank | Delete | Change block".to_owned(),
+                        "[Ctrl+h] Show Vim keymap".to_owned(),
+                        "[Esc] Cancel".to_owned(),
+                    ];
+                }
+
                 vec![
                     "[h|j|k|l] Move cursor".to_owned(),
                     "[1-9] Append steps".to_owned(),
@@ -406,9 +664,10 @@ impl NotebookState {
                     "[Esc] Cancel".to_owned(),
                 ]
             }
-            EditingInsertMode => {
+            EditingInsertMode | EditingReplaceMode => {
                 vec![
-                    "[Esc] Save note & Normal mode".to_owned(),
+                    "[Esc] Normal mode".to_owned(),
+                    "[Ctrl+s|Alt+s] Save | Save all".to_owned(),
                     "[Ctrl+h] Show editor keymap".to_owned(),
                 ]
             }
@@ -447,7 +706,28 @@ impl NotebookState {
     }
 }
 
+fn case_name(kind: CaseKind) -> &'static str {
+    match kind {
+        CaseKind::Toggle => "toggle case",
+        CaseKind::Lower => "lowercase",
+        CaseKind::Upper => "uppercase",
+    }
+}
+
 pub async fn consume(glues: &mut Glues, event: Event) -> Result<NotebookTransition> {
+    if let Event::Notebook(NotebookEvent::CloseNotebook { unsaved }) = event {
+        if let Some(db) = glues.db.as_mut() {
+            for (note_id, content) in unsaved {
+                note::update_content(db, note_id, content).await?;
+            }
+        }
+
+        glues.db = None;
+        glues.state = EntryState.into();
+
+        return Ok(NotebookTransition::CloseNotebook);
+    }
+
     let db = glues
         .db
         .as_mut()
@@ -129,7 +129,7 @@ pub async fn remove(
         ));
     }
 
-    db.remove_directory(directory.id.clone()).await?;
+    let note_ids = db.remove_directory(directory.id.clone()).await?;
 
     let selected_directory = state
         .root
@@ -145,6 +145,7 @@ pub async fn remove(
     Ok(NotebookTransition::RemoveDirectory {
         directory,
         selected_directory,
+        note_ids,
     })
 }
 
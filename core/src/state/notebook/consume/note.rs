@@ -1,6 +1,7 @@
 use crate::{
     data::{Directory, Note},
     db::Db,
+    state::notebook::directory,
     state::notebook::{DirectoryItem, InnerState, NotebookState, SelectedItem, VimNormalState},
     types::NoteId,
     Error, NotebookTransition, Result,
@@ -92,6 +93,8 @@ pub async fn open(
     note: Note,
 ) -> Result<NotebookTransition> {
     let content = db.fetch_note_content(note.id.clone()).await?;
+    // a note opened from the editor, such as by a mark, may be in a collapsed directory
+    directory::open_all(db, state, note.directory_id.clone()).await?;
 
     let i = state.tabs.iter().enumerate().find_map(|(i, tab_note)| {
         if tab_note.id == note.id {
@@ -113,6 +116,38 @@ pub async fn open(
     Ok(NotebookTransition::OpenNote { note, content })
 }
 
+/// Opens a note by its path from the root directory, such as `dir/note`.
+pub async fn open_path(
+    db: &mut Db,
+    state: &mut NotebookState,
+    path: &str,
+) -> Result<NotebookTransition> {
+    let mut names = path.split('/').filter(|name| !name.is_empty());
+    let note_name = names.next_back().unwrap_or_default();
+
+    let mut directory_id = db.root_id.clone();
+    for name in names {
+        let directories = db.fetch_directories(directory_id.clone()).await?;
+        match directories
+            .into_iter()
+            .find(|directory| directory.name == name)
+        {
+            Some(directory) => directory_id = directory.id,
+            None => return Ok(NotebookTransition::Alert(format!("Note not found: {path}"))),
+        }
+    }
+
+    let notes = db.fetch_notes(directory_id.clone()).await?;
+    let note = match notes.into_iter().find(|note| note.name == note_name) {
+        Some(note) => note,
+        None => return Ok(NotebookTransition::Alert(format!("Note not found: {path}"))),
+    };
+
+    state.selected = SelectedItem::Note(note.clone());
+
+    open(db, state, note).await
+}
+
 pub async fn view(state: &mut NotebookState) -> Result<NotebookTransition> {
     let note = state.get_editing()?.clone();
 
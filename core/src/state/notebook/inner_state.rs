@@ -2,18 +2,31 @@ mod directory_more_actions;
 mod directory_selected;
 mod editing_insert_mode;
 mod editing_normal_mode;
+mod editing_replace_mode;
 mod editing_visual_mode;
+mod macros;
 mod note_more_actions;
 mod note_selected;
 mod note_tree_number;
+mod repeat;
 
-use crate::{
-    db::Db,
-    state::notebook::{note, NotebookState},
-    Event, NotebookEvent, NotebookTransition, Result,
+pub use editing_normal_mode::{
+    Address, AddressBase, CaseKind, CharFind, FindAction, FindKind, LineRange, Motion, ObjectKind,
+    ObjectScope, SearchDirection, Substitution, TextObject, VimNormalState,
+};
+use {
+    crate::{
+        db::Db,
+        state::notebook::{note, tabs, NotebookState},
+        Event, KeyEvent, NotebookEvent, NotebookTransition, Result,
+    },
+    std::collections::VecDeque,
+};
+pub use {
+    editing_visual_mode::{VimVisualState, VisualKind},
+    macros::Macros,
+    repeat::Repeat,
 };
-pub use editing_normal_mode::VimNormalState;
-pub use editing_visual_mode::VimVisualState;
 
 #[derive(Clone, Copy)]
 pub enum InnerState {
@@ -25,18 +38,113 @@ pub enum InnerState {
     EditingNormalMode(VimNormalState),
     EditingVisualMode(VimVisualState),
     EditingInsertMode,
+    EditingReplaceMode,
 }
 
+/// Events replayed for a single key are capped, which stops a macro that runs itself.
+pub(super) const REPLAY_LIMIT: usize = 100_000;
+
 pub async fn consume(
     db: &mut Db,
     state: &mut NotebookState,
     event: Event,
 ) -> Result<NotebookTransition> {
-    use InnerState::*;
-
     if let Event::Notebook(NotebookEvent::UpdateNoteContent { note_id, content }) = event {
         return note::update_content(db, note_id, content).await;
     }
+    // not recorded, as the `:q` it comes of replays it
+    if let Event::Notebook(NotebookEvent::CloseTab) = event {
+        return tabs::close(db, state).await;
+    }
+
+    state.macros.record(&event);
+
+    // `.` and `@` expand into the events they replay, which may expand again
+    let mut events = VecDeque::from([event]);
+    let mut transitions = Vec::new();
+    let mut replayed = 0;
+    while let Some(event) = events.pop_front() {
+        let replay = match expand(state, &event) {
+            Some(replay) => replay,
+            None => {
+                match consume_recorded(db, state, event).await? {
+                    NotebookTransition::Batch(batch) => transitions.extend(batch),
+                    transition => transitions.push(transition),
+                }
+                continue;
+            }
+        };
+
+        replayed += replay.len();
+        if replayed > REPLAY_LIMIT {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+            transitions.push(NotebookTransition::Alert(
+                "Replay stopped, too many keys".to_owned(),
+            ));
+            break;
+        }
+
+        for event in replay.into_iter().rev() {
+            events.push_front(event);
+        }
+    }
+
+    Ok(match transitions.len() {
+        0 => NotebookTransition::None,
+        1 => transitions.remove(0),
+        _ => NotebookTransition::Batch(transitions),
+    })
+}
+
+/// The events `.` or `@{register}` stand for, `None` for any other event.
+fn expand(state: &mut NotebookState, event: &Event) -> Option<Vec<Event>> {
+    use InnerState::EditingNormalMode;
+
+    let replay = match (state.inner_state, event) {
+        (EditingNormalMode(VimNormalState::Idle), Event::Key(KeyEvent::Char('.'))) => {
+            state.repeat.replay(None)
+        }
+        (EditingNormalMode(VimNormalState::Numbering(n)), Event::Key(KeyEvent::Char('.'))) => {
+            state.repeat.replay(Some(n))
+        }
+        (EditingNormalMode(VimNormalState::PlayMacro(n)), Event::Key(KeyEvent::Char(c))) => {
+            state.repeat.discard();
+            state.macros.replay(*c, n)
+        }
+        _ => return None,
+    };
+
+    state.inner_state = EditingNormalMode(VimNormalState::Idle);
+
+    Some(replay)
+}
+
+async fn consume_recorded(
+    db: &mut Db,
+    state: &mut NotebookState,
+    event: Event,
+) -> Result<NotebookTransition> {
+    if !Repeat::is_recording(&state.inner_state) {
+        let transition = consume_state(db, state, event).await?;
+        if matches!(state.inner_state, InnerState::EditingInsertMode) {
+            state.repeat.skip();
+        }
+
+        return Ok(transition);
+    }
+
+    let transition = consume_state(db, state, event.clone()).await?;
+    state.repeat.record(event, &transition, &state.inner_state);
+
+    Ok(transition)
+}
+
+async fn consume_state(
+    db: &mut Db,
+    state: &mut NotebookState,
+    event: Event,
+) -> Result<NotebookTransition> {
+    use InnerState::*;
 
     match &state.inner_state {
         NoteSelected => note_selected::consume(db, state, event).await,
@@ -51,5 +159,6 @@ pub async fn consume(
             editing_visual_mode::consume(db, state, *vim_state, event).await
         }
         EditingInsertMode => editing_insert_mode::consume(db, state, event).await,
+        EditingReplaceMode => editing_replace_mode::consume(db, state, event).await,
     }
 }
@@ -1,7 +1,7 @@
 use crate::{
     db::Db,
     state::notebook::{directory, note, traverse, InnerState, NotebookState},
-    Error, Event, KeyEvent, NotebookEvent, NotebookTransition, Result,
+    Error, Event, KeyEvent, NotebookEvent, NotebookTransition, NumKey, Result,
 };
 
 pub async fn consume(
@@ -14,10 +14,10 @@ pub async fn consume(
 
     match event {
         Notebook(OpenDirectory(directory_id)) => directory::open(db, state, directory_id).await,
-        Key(KeyEvent::L | KeyEvent::Right | KeyEvent::Enter) => {
+        Key(KeyEvent::Char('l') | KeyEvent::Right | KeyEvent::Enter) => {
             let directory = state.get_selected_directory()?.clone();
             let directory_item = state.root.find(&directory.id).ok_or(Error::Wip(
-                "[Key::L] failed to find the target directory".to_owned(),
+                "[Key::l] failed to find the target directory".to_owned(),
             ))?;
 
             if directory_item.children.is_none() {
@@ -38,29 +38,30 @@ pub async fn consume(
 
             directory::close(state, directory)
         }
-        Key(KeyEvent::H) | Key(KeyEvent::Left) => {
+        Key(KeyEvent::Char('h')) | Key(KeyEvent::Left) => {
             let directory = state.get_selected_directory()?;
             if state.root.directory.id == directory.id {
                 return Ok(NotebookTransition::None);
             }
 
             let parent_item = state.root.find(&directory.parent_id).ok_or(Error::Wip(
-                "[Key::H] failed to find parent directory".to_owned(),
+                "[Key::h] failed to find parent directory".to_owned(),
             ))?;
             let parent = parent_item.directory.clone();
 
             directory::close(state, parent)
         }
-        Key(KeyEvent::J | KeyEvent::Down) => traverse::select_next(state),
-        Key(KeyEvent::K | KeyEvent::Up) => traverse::select_prev(state),
-        Key(KeyEvent::M) => {
+        Key(KeyEvent::Char('j') | KeyEvent::Down) => traverse::select_next(state),
+        Key(KeyEvent::Char('k') | KeyEvent::Up) => traverse::select_prev(state),
+        Key(KeyEvent::Char('m')) => {
             let directory = state.get_selected_directory()?.clone();
 
             directory::show_actions_dialog(state, directory)
         }
         Notebook(SelectNote(note)) => note::select(state, note),
         Notebook(SelectDirectory(directory)) => directory::select(state, directory),
-        Key(KeyEvent::Num(n)) => {
+        Key(KeyEvent::Char(c @ '0'..='9')) => {
+            let n = NumKey::try_from(c)?;
             state.inner_state = InnerState::NoteTreeNumber(n.into());
 
             Ok(NotebookTransition::None)
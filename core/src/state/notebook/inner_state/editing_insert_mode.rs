@@ -1,6 +1,7 @@
 use crate::{
     db::Db,
     state::notebook::{note, NotebookState},
+    transition::InsertModeTransition,
     Error, Event, KeyEvent, NotebookEvent, NotebookTransition, Result,
 };
 
@@ -14,7 +15,9 @@ pub async fn consume(
 
     match event {
         Key(KeyEvent::Esc) | Notebook(ViewNote) => note::view(state).await,
-        event @ Key(_) => Ok(NotebookTransition::Inedible(event)),
+        Key(key) => Ok(NotebookTransition::EditingInsertMode(
+            InsertModeTransition::Key(key),
+        )),
         _ => Err(Error::Wip("todo: Notebook::consume".to_owned())),
     }
 }
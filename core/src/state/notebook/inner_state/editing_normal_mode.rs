@@ -1,5 +1,11 @@
+mod case;
+mod command;
+mod find;
+mod motion;
+mod text_object;
+
 use {
-    super::VimVisualState,
+    super::{macros::is_macro_register, VimVisualState, VisualKind},
     crate::{
         db::Db,
         state::notebook::{directory, note, tabs, InnerState, NotebookState},
@@ -8,6 +14,15 @@ use {
         },
         Error, Event, KeyEvent, NotebookEvent, NumKey, Result,
     },
+    command::Command,
+};
+
+pub use {
+    case::CaseKind,
+    command::{Address, AddressBase, LineRange, Substitution},
+    find::{CharFind, FindAction, FindKind},
+    motion::Motion,
+    text_object::{ObjectKind, ObjectScope, TextObject},
 };
 
 #[derive(Clone, Copy)]
@@ -15,15 +30,33 @@ pub enum VimNormalState {
     Idle,
     Toggle,
     Numbering(usize),
-    Gateway,
+    Gateway(usize),
     Yank(usize),
     Yank2(usize, usize),
     Delete(usize),
     Delete2(usize, usize),
-    DeleteInside(usize),
     Change(usize),
     Change2(usize, usize),
-    ChangeInside(usize),
+    TextObject(usize, ObjectScope, FindAction),
+    Indent(usize),
+    Outdent(usize),
+    Case(usize, CaseKind),
+    ReplaceChar(usize),
+    Search(SearchDirection),
+    Command,
+    Register,
+    Find(usize, FindKind, FindAction),
+    RecordMacro,
+    PlayMacro(usize),
+    Mark,
+    /// Set for `'`, which jumps to the line of the mark rather than where it is in the line.
+    JumpToMark(bool),
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum SearchDirection {
+    Forward,
+    Backward,
 }
 
 pub async fn consume(
@@ -36,15 +69,28 @@ pub async fn consume(
         VimNormalState::Idle => consume_idle(state, event).await,
         VimNormalState::Toggle => consume_toggle(db, state, event).await,
         VimNormalState::Numbering(n) => consume_numbering(state, n, event).await,
-        VimNormalState::Gateway => consume_gateway(state, event).await,
+        VimNormalState::Gateway(n) => consume_gateway(state, n, event).await,
         VimNormalState::Yank(n) => consume_yank(state, n, event).await,
         VimNormalState::Yank2(n1, n2) => consume_yank2(state, n1, n2, event).await,
         VimNormalState::Delete(n) => consume_delete(state, n, event).await,
         VimNormalState::Delete2(n1, n2) => consume_delete2(state, n1, n2, event).await,
-        VimNormalState::DeleteInside(n) => consume_delete_inside(state, n, event).await,
         VimNormalState::Change(n) => consume_change(state, n, event).await,
         VimNormalState::Change2(n1, n2) => consume_change2(state, n1, n2, event).await,
-        VimNormalState::ChangeInside(n) => consume_change_inside(state, n, event).await,
+        VimNormalState::TextObject(n, scope, action) => {
+            consume_text_object(state, n, scope, action, event).await
+        }
+        VimNormalState::Indent(n) => consume_indent(state, n, true, event).await,
+        VimNormalState::Outdent(n) => consume_indent(state, n, false, event).await,
+        VimNormalState::Case(n, kind) => consume_case(state, n, kind, event).await,
+        VimNormalState::ReplaceChar(n) => consume_replace_char(state, n, event).await,
+        VimNormalState::Search(direction) => consume_search(state, direction, event).await,
+        VimNormalState::Command => consume_command(db, state, event).await,
+        VimNormalState::Register => consume_register(state, event).await,
+        VimNormalState::Find(n, kind, action) => consume_find(state, n, kind, action, event).await,
+        VimNormalState::RecordMacro => consume_record_macro(state, event).await,
+        VimNormalState::PlayMacro(_) => consume_play_macro(state, event).await,
+        VimNormalState::Mark => consume_mark(state, event).await,
+        VimNormalState::JumpToMark(linewise) => consume_jump_to_mark(state, linewise, event).await,
     }
 }
 
@@ -56,104 +102,217 @@ async fn consume_idle(state: &mut NotebookState, event: Event) -> Result<Noteboo
     match event {
         Notebook(NE::SelectNote(note)) => note::select(state, note),
         Notebook(NE::SelectDirectory(directory)) => directory::select(state, directory),
-        Key(KeyEvent::N) => {
+        Key(KeyEvent::Char('n')) if state.searching => SearchNext(1).into(),
+        Key(KeyEvent::Char('N')) if state.searching => SearchPrev(1).into(),
+        Key(KeyEvent::Esc) if state.searching => {
+            state.searching = false;
+
+            ClearSearch.into()
+        }
+        Key(KeyEvent::Char('/')) => {
+            state.inner_state =
+                InnerState::EditingNormalMode(VimNormalState::Search(SearchDirection::Forward));
+
+            SearchMode(SearchDirection::Forward).into()
+        }
+        Key(KeyEvent::Char('?')) => {
+            state.inner_state =
+                InnerState::EditingNormalMode(VimNormalState::Search(SearchDirection::Backward));
+
+            SearchMode(SearchDirection::Backward).into()
+        }
+        Key(KeyEvent::Char('"')) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Register);
+
+            RegisterMode.into()
+        }
+        Key(KeyEvent::Char(':')) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Command);
+
+            CommandMode.into()
+        }
+        Key(KeyEvent::Char('q')) => match state.macros.stop() {
+            Some(name) => StopRecording(name).into(),
+            None => {
+                state.inner_state = InnerState::EditingNormalMode(VimNormalState::RecordMacro);
+
+                RecordMacroMode.into()
+            }
+        },
+        Key(KeyEvent::Char('@')) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::PlayMacro(1));
+
+            PlayMacroMode.into()
+        }
+        Key(KeyEvent::Char('n')) => {
             state.inner_state = InnerState::NoteSelected;
 
             Ok(NotebookTransition::BrowseNoteTree)
         }
-        Key(KeyEvent::T) => {
+        Key(KeyEvent::Char(' ')) => {
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Toggle);
 
             ToggleMode.into()
         }
-        Key(KeyEvent::P) => Paste.into(),
-        Key(KeyEvent::U) => Undo.into(),
-        Key(KeyEvent::CtrlR) => Redo.into(),
-        Key(KeyEvent::J | KeyEvent::Down) => MoveCursorDown(1).into(),
-        Key(KeyEvent::K | KeyEvent::Up) => MoveCursorUp(1).into(),
-        Key(KeyEvent::H | KeyEvent::Left) => MoveCursorBack(1).into(),
-        Key(KeyEvent::L | KeyEvent::Right) => MoveCursorForward(1).into(),
-        Key(KeyEvent::W) => MoveCursorWordForward(1).into(),
-        Key(KeyEvent::E) => MoveCursorWordEnd(1).into(),
-        Key(KeyEvent::B) => MoveCursorWordBack(1).into(),
-        Key(KeyEvent::Num(NumKey::Zero)) => MoveCursorLineStart.into(),
-        Key(KeyEvent::DollarSign) => MoveCursorLineEnd.into(),
-        Key(KeyEvent::Caret) => MoveCursorLineNonEmptyStart.into(),
-        Key(KeyEvent::CapG) => MoveCursorBottom.into(),
-        Key(KeyEvent::I) => {
+        Key(KeyEvent::Char(c @ ('f' | 't' | 'F' | 'T'))) => {
+            let kind = FindKind::from_key(c);
+            state.inner_state =
+                InnerState::EditingNormalMode(VimNormalState::Find(1, kind, FindAction::Move));
+
+            FindMode.into()
+        }
+        Key(KeyEvent::Char(c @ (';' | ','))) => repeat_find(state, 1, c == ',', FindAction::Move),
+        Key(KeyEvent::Char(c @ ('{' | '}' | '(' | ')' | '%' | 'H' | 'M' | 'L'))) => {
+            MoveCursor(Motion::from_key(c), 1).into()
+        }
+        Key(KeyEvent::Char('m')) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Mark);
+
+            MarkMode.into()
+        }
+        Key(KeyEvent::Char(c @ ('\'' | '`'))) => {
+            state.inner_state =
+                InnerState::EditingNormalMode(VimNormalState::JumpToMark(c == '\''));
+
+            JumpToMarkMode.into()
+        }
+        // terminals send Ctrl+i as Tab
+        Key(KeyEvent::Ctrl('o')) => JumpBack(1).into(),
+        Key(KeyEvent::Ctrl('i') | KeyEvent::Tab) => JumpForward(1).into(),
+        Key(KeyEvent::Ctrl('d')) => ScrollHalfPageDown(1).into(),
+        Key(KeyEvent::Ctrl('u')) => ScrollHalfPageUp(1).into(),
+        Key(KeyEvent::Ctrl('f')) => ScrollPageDown(1).into(),
+        Key(KeyEvent::Ctrl('b')) => ScrollPageUp(1).into(),
+        Key(KeyEvent::Char('p')) => Paste.into(),
+        Key(KeyEvent::Char('u')) => Undo.into(),
+        Key(KeyEvent::Ctrl('r')) => Redo.into(),
+        Key(KeyEvent::Char('j') | KeyEvent::Down) => MoveCursorDown(1).into(),
+        Key(KeyEvent::Char('k') | KeyEvent::Up) => MoveCursorUp(1).into(),
+        Key(KeyEvent::Char('h') | KeyEvent::Left) => MoveCursorBack(1).into(),
+        Key(KeyEvent::Char('l') | KeyEvent::Right) => MoveCursorForward(1).into(),
+        Key(KeyEvent::Char('w')) => MoveCursorWordForward(1).into(),
+        Key(KeyEvent::Char('e')) => MoveCursorWordEnd(1).into(),
+        Key(KeyEvent::Char('b')) => MoveCursorWordBack(1).into(),
+        Key(KeyEvent::Char('0')) => MoveCursorLineStart.into(),
+        Key(KeyEvent::Char('$')) => MoveCursorLineEnd.into(),
+        Key(KeyEvent::Char('^')) => MoveCursorLineNonEmptyStart.into(),
+        Key(KeyEvent::Char('G')) => MoveCursorBottom.into(),
+        Key(KeyEvent::Char('i')) => {
             state.inner_state = InnerState::EditingInsertMode;
 
             InsertAtCursor.into()
         }
-        Key(KeyEvent::V) => {
+        Key(KeyEvent::Char('v')) => {
             state.inner_state = InnerState::EditingVisualMode(VimVisualState::Idle);
+            state.visual_kind = VisualKind::Charwise;
 
             Ok(NotebookTransition::EditingVisualMode(
                 VisualModeTransition::IdleMode,
             ))
         }
-        Key(KeyEvent::CapI) => {
+        Key(KeyEvent::Char('V')) => {
+            state.inner_state = InnerState::EditingVisualMode(VimVisualState::Idle);
+            state.visual_kind = VisualKind::Linewise;
+
+            Ok(NotebookTransition::EditingVisualMode(
+                VisualModeTransition::LinewiseMode,
+            ))
+        }
+        Key(KeyEvent::Ctrl('v')) => {
+            state.inner_state = InnerState::EditingVisualMode(VimVisualState::Idle);
+            state.visual_kind = VisualKind::Blockwise;
+
+            Ok(NotebookTransition::EditingVisualMode(
+                VisualModeTransition::BlockwiseMode,
+            ))
+        }
+        Key(KeyEvent::Char('I')) => {
             state.inner_state = InnerState::EditingInsertMode;
 
             InsertAtLineStart.into()
         }
-        Key(KeyEvent::A) => {
+        Key(KeyEvent::Char('a')) => {
             state.inner_state = InnerState::EditingInsertMode;
 
             InsertAfterCursor.into()
         }
-        Key(KeyEvent::CapA) => {
+        Key(KeyEvent::Char('A')) => {
             state.inner_state = InnerState::EditingInsertMode;
 
             InsertAtLineEnd.into()
         }
-        Key(KeyEvent::O) => {
+        Key(KeyEvent::Char('o')) => {
             state.inner_state = InnerState::EditingInsertMode;
 
             InsertNewLineBelow.into()
         }
-        Key(KeyEvent::CapO) => {
+        Key(KeyEvent::Char('O')) => {
             state.inner_state = InnerState::EditingInsertMode;
 
             InsertNewLineAbove.into()
         }
-        Key(KeyEvent::G) => {
-            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Gateway);
+        Key(KeyEvent::Char('R')) => {
+            state.inner_state = InnerState::EditingReplaceMode;
+
+            ReplaceMode.into()
+        }
+        Key(KeyEvent::Char('g')) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Gateway(1));
 
             GatewayMode.into()
         }
-        Key(KeyEvent::Y) => {
+        Key(KeyEvent::Char('y')) => {
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Yank(1));
 
             YankMode.into()
         }
-        Key(KeyEvent::D) => {
+        Key(KeyEvent::Char('d')) => {
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Delete(1));
 
             DeleteMode.into()
         }
-        Key(KeyEvent::C) => {
+        Key(KeyEvent::Char('c')) => {
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Change(1));
 
             ChangeMode.into()
         }
-        Key(KeyEvent::X) => DeleteChars(1).into(),
-        Key(KeyEvent::S) => {
+        Key(KeyEvent::Char('>')) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Indent(1));
+
+            IndentMode.into()
+        }
+        Key(KeyEvent::Char('<')) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Outdent(1));
+
+            OutdentMode.into()
+        }
+        Key(KeyEvent::Char('r')) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::ReplaceChar(1));
+
+            ReplaceCharMode.into()
+        }
+        Key(KeyEvent::Char('x')) => DeleteChars(1).into(),
+        Key(KeyEvent::Char('~')) => CaseChars(CaseKind::Toggle, 1).into(),
+        Key(KeyEvent::Char('J')) => JoinLines(2).into(),
+        Key(KeyEvent::Char('s')) => {
             state.inner_state = InnerState::EditingInsertMode;
 
             DeleteChars(1).into()
         }
-        Key(KeyEvent::CapS) => {
+        Key(KeyEvent::Char('S')) => {
             state.inner_state = InnerState::EditingInsertMode;
 
             DeleteLines(1).into()
         }
-        Key(KeyEvent::Num(n)) => {
+        Key(KeyEvent::Char(c @ '0'..='9')) => {
+            let n = NumKey::try_from(c)?;
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Numbering(n.into()));
 
             NumberingMode.into()
         }
-        Key(KeyEvent::CtrlH) => Ok(NotebookTransition::ShowVimKeymap(VimKeymapKind::NormalIdle)),
+        Key(KeyEvent::Ctrl('h')) => {
+            Ok(NotebookTransition::ShowVimKeymap(VimKeymapKind::NormalIdle))
+        }
         event @ Key(_) => Ok(NotebookTransition::Inedible(event)),
         _ => Err(Error::Wip("todo: Notebook::consume".to_owned())),
     }
@@ -168,19 +327,24 @@ async fn consume_toggle(
     use NormalModeTransition::*;
 
     match event {
-        Key(KeyEvent::L | KeyEvent::Right) => tabs::select_prev(db, state).await,
-        Key(KeyEvent::H | KeyEvent::Left) => tabs::select_next(db, state).await,
-        Key(KeyEvent::X) => tabs::close(db, state).await,
-        Key(KeyEvent::N) => {
+        Key(KeyEvent::Char('l') | KeyEvent::Right) => tabs::select_prev(db, state).await,
+        Key(KeyEvent::Char('h') | KeyEvent::Left) => tabs::select_next(db, state).await,
+        Key(KeyEvent::Char('x')) => tabs::close(db, state).await,
+        Key(KeyEvent::Char('n')) => {
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
 
             ToggleLineNumbers.into()
         }
-        Key(KeyEvent::B) => {
+        Key(KeyEvent::Char('b')) => {
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
 
             ToggleBrowser.into()
         }
+        Key(KeyEvent::Esc) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+            IdleMode.into()
+        }
         event @ Key(_) => {
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
 
@@ -199,78 +363,172 @@ async fn consume_numbering(
     use NormalModeTransition::*;
 
     match event {
-        Key(KeyEvent::Num(n2)) => {
+        Key(KeyEvent::Char(c @ '0'..='9')) => {
+            let n2 = NumKey::try_from(c)?;
             let step = n2 + n.saturating_mul(10);
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Numbering(step));
 
             Ok(NotebookTransition::None)
         }
-        Key(KeyEvent::J | KeyEvent::Down) => {
+        Key(KeyEvent::Char('j') | KeyEvent::Down) => {
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
 
             MoveCursorDown(n).into()
         }
-        Key(KeyEvent::K | KeyEvent::Up) => {
+        Key(KeyEvent::Char('k') | KeyEvent::Up) => {
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
 
             MoveCursorUp(n).into()
         }
-        Key(KeyEvent::H | KeyEvent::Left) => {
+        Key(KeyEvent::Char('h') | KeyEvent::Left) => {
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
 
             NormalModeTransition::MoveCursorBack(n).into()
         }
-        Key(KeyEvent::L | KeyEvent::Right) => {
+        Key(KeyEvent::Char('l') | KeyEvent::Right) => {
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
 
             MoveCursorForward(n).into()
         }
-        Key(KeyEvent::W) => {
+        Key(KeyEvent::Char('w')) => {
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
 
             MoveCursorWordForward(n).into()
         }
-        Key(KeyEvent::E) => {
+        Key(KeyEvent::Char('e')) => {
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
 
             MoveCursorWordEnd(n).into()
         }
-        Key(KeyEvent::B) => {
+        Key(KeyEvent::Char('b')) => {
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
 
             MoveCursorWordBack(n).into()
         }
-        Key(KeyEvent::CapG) => {
+        Key(KeyEvent::Char('G')) => {
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
 
             MoveCursorToLine(n).into()
         }
-        Key(KeyEvent::X) => {
+        Key(KeyEvent::Char('%')) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+            MoveCursor(Motion::Percent, n).into()
+        }
+        Key(KeyEvent::Char(c @ ('{' | '}' | '(' | ')' | 'H' | 'M' | 'L'))) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+            MoveCursor(Motion::from_key(c), n).into()
+        }
+        Key(KeyEvent::Ctrl('o')) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+            JumpBack(n).into()
+        }
+        Key(KeyEvent::Ctrl('i') | KeyEvent::Tab) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+            JumpForward(n).into()
+        }
+        Key(KeyEvent::Ctrl('d')) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+            ScrollHalfPageDown(n).into()
+        }
+        Key(KeyEvent::Ctrl('u')) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+            ScrollHalfPageUp(n).into()
+        }
+        Key(KeyEvent::Ctrl('f')) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+            ScrollPageDown(n).into()
+        }
+        Key(KeyEvent::Ctrl('b')) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+            ScrollPageUp(n).into()
+        }
+        Key(KeyEvent::Char('n')) if state.searching => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+            SearchNext(n).into()
+        }
+        Key(KeyEvent::Char('N')) if state.searching => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+            SearchPrev(n).into()
+        }
+        Key(KeyEvent::Char('x')) => {
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
 
             DeleteChars(n).into()
         }
-        Key(KeyEvent::S) => {
+        Key(KeyEvent::Char('~')) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+            CaseChars(CaseKind::Toggle, n).into()
+        }
+        Key(KeyEvent::Char('J')) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+            JoinLines(n.max(2)).into()
+        }
+        Key(KeyEvent::Char('>')) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Indent(n));
+
+            IndentMode.into()
+        }
+        Key(KeyEvent::Char('<')) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Outdent(n));
+
+            OutdentMode.into()
+        }
+        Key(KeyEvent::Char('r')) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::ReplaceChar(n));
+
+            ReplaceCharMode.into()
+        }
+        Key(KeyEvent::Char('g')) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Gateway(n));
+
+            GatewayMode.into()
+        }
+        Key(KeyEvent::Char('@')) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::PlayMacro(n));
+
+            PlayMacroMode.into()
+        }
+        Key(KeyEvent::Char('s')) => {
             state.inner_state = InnerState::EditingInsertMode;
 
             DeleteChars(n).into()
         }
-        Key(KeyEvent::CapS) => {
+        Key(KeyEvent::Char('S')) => {
             state.inner_state = InnerState::EditingInsertMode;
 
             DeleteLines(n).into()
         }
-        Key(KeyEvent::Y) => {
+        Key(KeyEvent::Char(c @ ('f' | 't' | 'F' | 'T'))) => {
+            let kind = FindKind::from_key(c);
+            state.inner_state =
+                InnerState::EditingNormalMode(VimNormalState::Find(n, kind, FindAction::Move));
+
+            FindMode.into()
+        }
+        Key(KeyEvent::Char(c @ (';' | ','))) => repeat_find(state, n, c == ',', FindAction::Move),
+        Key(KeyEvent::Char('y')) => {
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Yank(n));
 
             YankMode.into()
         }
-        Key(KeyEvent::D) => {
+        Key(KeyEvent::Char('d')) => {
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Delete(n));
 
             DeleteMode.into()
         }
-        Key(KeyEvent::C) => {
+        Key(KeyEvent::Char('c')) => {
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Change(n));
 
             ChangeMode.into()
@@ -280,7 +538,7 @@ async fn consume_numbering(
 
             IdleMode.into()
         }
-        Key(KeyEvent::CtrlH) => Ok(NotebookTransition::ShowVimKeymap(
+        Key(KeyEvent::Ctrl('h')) => Ok(NotebookTransition::ShowVimKeymap(
             VimKeymapKind::NormalNumbering,
         )),
         event @ Key(_) => {
@@ -292,16 +550,31 @@ async fn consume_numbering(
     }
 }
 
-async fn consume_gateway(state: &mut NotebookState, event: Event) -> Result<NotebookTransition> {
+async fn consume_gateway(
+    state: &mut NotebookState,
+    n: usize,
+    event: Event,
+) -> Result<NotebookTransition> {
     use Event::*;
     use NormalModeTransition::*;
 
     match event {
-        Key(KeyEvent::G) => {
+        Key(KeyEvent::Char('g')) if n >= 2 => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+            MoveCursorToLine(n).into()
+        }
+        Key(KeyEvent::Char('g')) => {
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
 
             NormalModeTransition::MoveCursorTop.into()
         }
+        Key(KeyEvent::Char(c @ ('~' | 'u' | 'U'))) => {
+            let kind = CaseKind::from_key(c);
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Case(n, kind));
+
+            CaseMode.into()
+        }
         Key(KeyEvent::Esc) => {
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
 
@@ -325,16 +598,40 @@ async fn consume_yank(
     use NormalModeTransition::*;
 
     match event {
-        Key(KeyEvent::Num(n2)) if !matches!(n2, NumKey::Zero) => {
+        Key(KeyEvent::Char(c @ '1'..='9')) => {
+            let n2 = NumKey::try_from(c)?;
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Yank2(n, n2.into()));
 
             Ok(NotebookTransition::None)
         }
-        Key(KeyEvent::Y) => {
+        Key(KeyEvent::Char('y')) => {
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
 
             YankLines(n).into()
         }
+        Key(KeyEvent::Char(c @ ('i' | 'a'))) => {
+            let scope = ObjectScope::from_key(c);
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::TextObject(
+                n,
+                scope,
+                FindAction::Yank,
+            ));
+
+            TextObjectMode.into()
+        }
+        Key(KeyEvent::Char(c @ ('{' | '}' | '(' | ')' | '%' | 'H' | 'M' | 'L'))) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+            YankMotion(Motion::from_counted_key(c, n > 1), n).into()
+        }
+        Key(KeyEvent::Char(c @ ('f' | 't' | 'F' | 'T'))) => {
+            let kind = FindKind::from_key(c);
+            state.inner_state =
+                InnerState::EditingNormalMode(VimNormalState::Find(n, kind, FindAction::Yank));
+
+            FindMode.into()
+        }
+        Key(KeyEvent::Char(c @ (';' | ','))) => repeat_find(state, n, c == ',', FindAction::Yank),
         Key(KeyEvent::Esc) => {
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
 
@@ -359,16 +656,45 @@ async fn consume_yank2(
     use NormalModeTransition::*;
 
     match event {
-        Key(KeyEvent::Num(n)) => {
+        Key(KeyEvent::Char(c @ '0'..='9')) => {
+            let n = NumKey::try_from(c)?;
             let n2 = n + n2.saturating_mul(10);
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Yank2(n1, n2));
 
             Ok(NotebookTransition::None)
         }
-        Key(KeyEvent::Y) => {
+        Key(KeyEvent::Char('y')) => {
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
 
-            YankLines(n1 * n2).into()
+            YankLines(n1.saturating_mul(n2)).into()
+        }
+        Key(KeyEvent::Char(c @ ('i' | 'a'))) => {
+            let scope = ObjectScope::from_key(c);
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::TextObject(
+                n1.saturating_mul(n2),
+                scope,
+                FindAction::Yank,
+            ));
+
+            TextObjectMode.into()
+        }
+        Key(KeyEvent::Char(c @ ('{' | '}' | '(' | ')' | '%' | 'H' | 'M' | 'L'))) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+            YankMotion(Motion::from_counted_key(c, true), n1.saturating_mul(n2)).into()
+        }
+        Key(KeyEvent::Char(c @ ('f' | 't' | 'F' | 'T'))) => {
+            let kind = FindKind::from_key(c);
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Find(
+                n1.saturating_mul(n2),
+                kind,
+                FindAction::Yank,
+            ));
+
+            FindMode.into()
+        }
+        Key(KeyEvent::Char(c @ (';' | ','))) => {
+            repeat_find(state, n1.saturating_mul(n2), c == ',', FindAction::Yank)
         }
         Key(KeyEvent::Esc) => {
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
@@ -393,47 +719,66 @@ async fn consume_delete(
     use NormalModeTransition::*;
 
     match event {
-        Key(KeyEvent::Num(NumKey::Zero)) => {
+        Key(KeyEvent::Char('0')) => {
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
 
             DeleteLineStart.into()
         }
-        Key(KeyEvent::Num(n2)) => {
+        Key(KeyEvent::Char(c @ '0'..='9')) => {
+            let n2 = NumKey::try_from(c)?;
             state.inner_state =
                 InnerState::EditingNormalMode(VimNormalState::Delete2(n, n2.into()));
 
             Ok(NotebookTransition::None)
         }
-        Key(KeyEvent::D) => {
+        Key(KeyEvent::Char('d')) => {
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
 
             DeleteLines(n).into()
         }
-        Key(KeyEvent::B) => {
+        Key(KeyEvent::Char('b')) => {
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
 
             DeleteWordBack(n).into()
         }
-        Key(KeyEvent::E) => {
+        Key(KeyEvent::Char('e')) => {
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
             DeleteWordEnd(n).into()
         }
-        Key(KeyEvent::DollarSign) => {
+        Key(KeyEvent::Char('$')) => {
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
 
             DeleteLineEnd(n).into()
         }
-        Key(KeyEvent::I) => {
-            state.inner_state = InnerState::EditingNormalMode(VimNormalState::DeleteInside(n));
+        Key(KeyEvent::Char(c @ ('i' | 'a'))) => {
+            let scope = ObjectScope::from_key(c);
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::TextObject(
+                n,
+                scope,
+                FindAction::Delete,
+            ));
+
+            TextObjectMode.into()
+        }
+        Key(KeyEvent::Char(c @ ('{' | '}' | '(' | ')' | '%' | 'H' | 'M' | 'L'))) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+            DeleteMotion(Motion::from_counted_key(c, n > 1), n).into()
+        }
+        Key(KeyEvent::Char(c @ ('f' | 't' | 'F' | 'T'))) => {
+            let kind = FindKind::from_key(c);
+            state.inner_state =
+                InnerState::EditingNormalMode(VimNormalState::Find(n, kind, FindAction::Delete));
 
-            DeleteInsideMode.into()
+            FindMode.into()
         }
+        Key(KeyEvent::Char(c @ (';' | ','))) => repeat_find(state, n, c == ',', FindAction::Delete),
         Key(KeyEvent::Esc) => {
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
 
             IdleMode.into()
         }
-        Key(KeyEvent::CtrlH) => Ok(NotebookTransition::ShowVimKeymap(
+        Key(KeyEvent::Ctrl('h')) => Ok(NotebookTransition::ShowVimKeymap(
             VimKeymapKind::NormalDelete,
         )),
         event @ Key(_) => {
@@ -455,54 +800,54 @@ async fn consume_delete2(
     use NormalModeTransition::*;
 
     match event {
-        Key(KeyEvent::Num(n)) => {
+        Key(KeyEvent::Char(c @ '0'..='9')) => {
+            let n = NumKey::try_from(c)?;
             let n2 = n + n2.saturating_mul(10);
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Delete2(n1, n2));
 
             Ok(NotebookTransition::None)
         }
-        Key(KeyEvent::D) => {
+        Key(KeyEvent::Char('d')) => {
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
 
-            DeleteLines(n1 * n2).into()
+            DeleteLines(n1.saturating_mul(n2)).into()
         }
-        Key(KeyEvent::I) => {
-            state.inner_state =
-                InnerState::EditingNormalMode(VimNormalState::DeleteInside(n1 * n2));
+        Key(KeyEvent::Char(c @ ('i' | 'a'))) => {
+            let scope = ObjectScope::from_key(c);
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::TextObject(
+                n1.saturating_mul(n2),
+                scope,
+                FindAction::Delete,
+            ));
 
-            DeleteInsideMode.into()
+            TextObjectMode.into()
         }
-        Key(KeyEvent::Esc) => {
+        Key(KeyEvent::Char(c @ ('{' | '}' | '(' | ')' | '%' | 'H' | 'M' | 'L'))) => {
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
 
-            IdleMode.into()
+            DeleteMotion(Motion::from_counted_key(c, true), n1.saturating_mul(n2)).into()
         }
-        Key(KeyEvent::CtrlH) => Ok(NotebookTransition::ShowVimKeymap(
-            VimKeymapKind::NormalDelete2,
-        )),
-        event @ Key(_) => {
-            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+        Key(KeyEvent::Char(c @ ('f' | 't' | 'F' | 'T'))) => {
+            let kind = FindKind::from_key(c);
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Find(
+                n1.saturating_mul(n2),
+                kind,
+                FindAction::Delete,
+            ));
 
-            consume_idle(state, event).await
+            FindMode.into()
         }
-        _ => Err(Error::Wip("todo: Notebook::consume".to_owned())),
-    }
-}
-
-async fn consume_delete_inside(
-    state: &mut NotebookState,
-    n: usize,
-    event: Event,
-) -> Result<NotebookTransition> {
-    use Event::*;
-    use NormalModeTransition::*;
-
-    match event {
-        Key(KeyEvent::W) => {
+        Key(KeyEvent::Char(c @ (';' | ','))) => {
+            repeat_find(state, n1.saturating_mul(n2), c == ',', FindAction::Delete)
+        }
+        Key(KeyEvent::Esc) => {
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
 
-            DeleteInsideWord(n).into()
+            IdleMode.into()
         }
+        Key(KeyEvent::Ctrl('h')) => Ok(NotebookTransition::ShowVimKeymap(
+            VimKeymapKind::NormalDelete2,
+        )),
         event @ Key(_) => {
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
 
@@ -521,39 +866,58 @@ async fn consume_change(
     use NormalModeTransition::*;
 
     match event {
-        Key(KeyEvent::Num(NumKey::Zero)) => {
+        Key(KeyEvent::Char('0')) => {
             state.inner_state = InnerState::EditingInsertMode;
 
             DeleteLineStart.into()
         }
-        Key(KeyEvent::Num(n2)) => {
+        Key(KeyEvent::Char(c @ '0'..='9')) => {
+            let n2 = NumKey::try_from(c)?;
             state.inner_state =
                 InnerState::EditingNormalMode(VimNormalState::Change2(n, n2.into()));
 
             Ok(NotebookTransition::None)
         }
-        Key(KeyEvent::I) => {
-            state.inner_state = InnerState::EditingNormalMode(VimNormalState::ChangeInside(n));
+        Key(KeyEvent::Char(c @ ('i' | 'a'))) => {
+            let scope = ObjectScope::from_key(c);
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::TextObject(
+                n,
+                scope,
+                FindAction::Change,
+            ));
 
-            ChangeInsideMode.into()
+            TextObjectMode.into()
         }
-        Key(KeyEvent::C) => {
+        Key(KeyEvent::Char('c')) => {
             state.inner_state = InnerState::EditingInsertMode;
             DeleteLinesAndInsert(n).into()
         }
-        Key(KeyEvent::E | KeyEvent::W) => {
+        Key(KeyEvent::Char('e' | 'w')) => {
             state.inner_state = InnerState::EditingInsertMode;
             DeleteWordEnd(n).into()
         }
-        Key(KeyEvent::B) => {
+        Key(KeyEvent::Char('b')) => {
             state.inner_state = InnerState::EditingInsertMode;
             DeleteWordBack(n).into()
         }
-        Key(KeyEvent::DollarSign) => {
+        Key(KeyEvent::Char('$')) => {
             state.inner_state = InnerState::EditingInsertMode;
             DeleteLineEnd(n).into()
         }
-        Key(KeyEvent::CtrlH) => Ok(NotebookTransition::ShowVimKeymap(
+        Key(KeyEvent::Char(c @ ('{' | '}' | '(' | ')' | '%' | 'H' | 'M' | 'L'))) => {
+            state.inner_state = InnerState::EditingInsertMode;
+
+            ChangeMotion(Motion::from_counted_key(c, n > 1), n).into()
+        }
+        Key(KeyEvent::Char(c @ ('f' | 't' | 'F' | 'T'))) => {
+            let kind = FindKind::from_key(c);
+            state.inner_state =
+                InnerState::EditingNormalMode(VimNormalState::Find(n, kind, FindAction::Change));
+
+            FindMode.into()
+        }
+        Key(KeyEvent::Char(c @ (';' | ','))) => repeat_find(state, n, c == ',', FindAction::Change),
+        Key(KeyEvent::Ctrl('h')) => Ok(NotebookTransition::ShowVimKeymap(
             VimKeymapKind::NormalChange,
         )),
         event @ Key(_) => {
@@ -575,39 +939,62 @@ async fn consume_change2(
     use NormalModeTransition::*;
 
     match event {
-        Key(KeyEvent::Num(n)) => {
+        Key(KeyEvent::Char(c @ '0'..='9')) => {
+            let n = NumKey::try_from(c)?;
             let n2 = n + n2.saturating_mul(10);
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Change2(n1, n2));
 
             Ok(NotebookTransition::None)
         }
-        Key(KeyEvent::I) => {
-            let n = n1.saturating_mul(n2);
-            state.inner_state = InnerState::EditingNormalMode(VimNormalState::ChangeInside(n));
+        Key(KeyEvent::Char(c @ ('i' | 'a'))) => {
+            let scope = ObjectScope::from_key(c);
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::TextObject(
+                n1.saturating_mul(n2),
+                scope,
+                FindAction::Change,
+            ));
 
-            ChangeInsideMode.into()
+            TextObjectMode.into()
         }
-        Key(KeyEvent::C) => {
+        Key(KeyEvent::Char('c')) => {
             let n = n1.saturating_mul(n2);
             state.inner_state = InnerState::EditingInsertMode;
             DeleteLinesAndInsert(n).into()
         }
-        Key(KeyEvent::E | KeyEvent::W) => {
+        Key(KeyEvent::Char('e' | 'w')) => {
             let n = n1.saturating_mul(n2);
             state.inner_state = InnerState::EditingInsertMode;
             DeleteWordEnd(n).into()
         }
-        Key(KeyEvent::B) => {
+        Key(KeyEvent::Char('b')) => {
             let n = n1.saturating_mul(n2);
             state.inner_state = InnerState::EditingInsertMode;
             DeleteWordBack(n).into()
         }
-        Key(KeyEvent::DollarSign) => {
+        Key(KeyEvent::Char('$')) => {
             let n = n1.saturating_mul(n2);
             state.inner_state = InnerState::EditingInsertMode;
             DeleteLineEnd(n).into()
         }
-        Key(KeyEvent::CtrlH) => Ok(NotebookTransition::ShowVimKeymap(
+        Key(KeyEvent::Char(c @ ('{' | '}' | '(' | ')' | '%' | 'H' | 'M' | 'L'))) => {
+            state.inner_state = InnerState::EditingInsertMode;
+
+            ChangeMotion(Motion::from_counted_key(c, true), n1.saturating_mul(n2)).into()
+        }
+        Key(KeyEvent::Char(c @ ('f' | 't' | 'F' | 'T'))) => {
+            let kind = FindKind::from_key(c);
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Find(
+                n1.saturating_mul(n2),
+                kind,
+                FindAction::Change,
+            ));
+
+            FindMode.into()
+        }
+        Key(KeyEvent::Char(c @ (';' | ','))) => {
+            repeat_find(state, n1.saturating_mul(n2), c == ',', FindAction::Change)
+        }
+        Key(KeyEvent::Ctrl('h')) => Ok(NotebookTransition::ShowVimKeymap(
             VimKeymapKind::NormalChange2,
         )),
         Key(KeyEvent::Esc) => {
@@ -624,19 +1011,143 @@ async fn consume_change2(
     }
 }
 
-async fn consume_change_inside(
+async fn consume_text_object(
     state: &mut NotebookState,
     n: usize,
+    scope: ObjectScope,
+    action: FindAction,
     event: Event,
 ) -> Result<NotebookTransition> {
     use Event::*;
     use NormalModeTransition::*;
 
-    match event {
-        Key(KeyEvent::W) => {
+    state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+    let kind = match event {
+        Key(KeyEvent::Char(c)) => ObjectKind::from_key(c),
+        Key(_) => None,
+        _ => return Err(Error::Wip("todo: Notebook::consume".to_owned())),
+    };
+    let object = match kind {
+        Some(kind) => TextObject { scope, kind },
+        None => return IdleMode.into(),
+    };
+
+    match action {
+        FindAction::Delete => DeleteTextObject(object, n).into(),
+        FindAction::Change => {
             state.inner_state = InnerState::EditingInsertMode;
 
-            DeleteInsideWord(n).into()
+            ChangeTextObject(object, n).into()
+        }
+        FindAction::Yank => YankTextObject(object, n).into(),
+        FindAction::Case(kind) => CaseTextObject(kind, object, n).into(),
+        FindAction::Move => IdleMode.into(),
+    }
+}
+
+/// `>>` and `<<`, where `indent` tells which of the two has been started.
+async fn consume_indent(
+    state: &mut NotebookState,
+    n: usize,
+    indent: bool,
+    event: Event,
+) -> Result<NotebookTransition> {
+    use Event::*;
+    use NormalModeTransition::*;
+
+    match event {
+        Key(KeyEvent::Char('>')) if indent => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+            IndentLines(n).into()
+        }
+        Key(KeyEvent::Char('<')) if !indent => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+            OutdentLines(n).into()
+        }
+        Key(KeyEvent::Esc) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+            IdleMode.into()
+        }
+        event @ Key(_) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+            consume_idle(state, event).await
+        }
+        _ => Err(Error::Wip("todo: Notebook::consume".to_owned())),
+    }
+}
+
+async fn consume_case(
+    state: &mut NotebookState,
+    n: usize,
+    kind: CaseKind,
+    event: Event,
+) -> Result<NotebookTransition> {
+    use Event::*;
+    use NormalModeTransition::*;
+
+    match event {
+        Key(KeyEvent::Char(c)) if c == kind.key() => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+            CaseLines(kind, n).into()
+        }
+        Key(KeyEvent::Char('e' | 'w')) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+            CaseWordEnd(kind, n).into()
+        }
+        Key(KeyEvent::Char('b')) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+            CaseWordBack(kind, n).into()
+        }
+        Key(KeyEvent::Char('0')) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+            CaseLineStart(kind).into()
+        }
+        Key(KeyEvent::Char('$')) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+            CaseLineEnd(kind, n).into()
+        }
+        Key(KeyEvent::Char(c @ ('i' | 'a'))) => {
+            let scope = ObjectScope::from_key(c);
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::TextObject(
+                n,
+                scope,
+                FindAction::Case(kind),
+            ));
+
+            TextObjectMode.into()
+        }
+        Key(KeyEvent::Char(c @ ('{' | '}' | '(' | ')' | '%' | 'H' | 'M' | 'L'))) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+            CaseMotion(kind, Motion::from_counted_key(c, n > 1), n).into()
+        }
+        Key(KeyEvent::Char(c @ ('f' | 't' | 'F' | 'T'))) => {
+            let find_kind = FindKind::from_key(c);
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Find(
+                n,
+                find_kind,
+                FindAction::Case(kind),
+            ));
+
+            FindMode.into()
+        }
+        Key(KeyEvent::Char(c @ (';' | ','))) => {
+            repeat_find(state, n, c == ',', FindAction::Case(kind))
+        }
+        Key(KeyEvent::Esc) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+            IdleMode.into()
         }
         event @ Key(_) => {
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
@@ -647,8 +1158,263 @@ async fn consume_change_inside(
     }
 }
 
+/// `r` takes the next key as the character to write over the `n` under and after the cursor.
+async fn consume_replace_char(
+    state: &mut NotebookState,
+    n: usize,
+    event: Event,
+) -> Result<NotebookTransition> {
+    use Event::*;
+    use NormalModeTransition::*;
+
+    state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+    match event {
+        Key(KeyEvent::Char(c)) => ReplaceChars(c, n).into(),
+        Key(_) => IdleMode.into(),
+        _ => Err(Error::Wip("todo: Notebook::consume".to_owned())),
+    }
+}
+
 impl From<NormalModeTransition> for Result<NotebookTransition> {
     fn from(transition: NormalModeTransition) -> Self {
         Ok(NotebookTransition::EditingNormalMode(transition))
     }
 }
+
+async fn consume_search(
+    state: &mut NotebookState,
+    direction: SearchDirection,
+    event: Event,
+) -> Result<NotebookTransition> {
+    use Event::*;
+    use NormalModeTransition::*;
+
+    match event {
+        Notebook(NotebookEvent::Search(pattern)) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+            state.searching = true;
+
+            ConfirmSearch(direction, pattern).into()
+        }
+        Key(KeyEvent::Esc) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+            CancelSearch.into()
+        }
+        event @ Key(_) => Ok(NotebookTransition::Inedible(event)),
+        _ => Err(Error::Wip("todo: Notebook::consume".to_owned())),
+    }
+}
+
+async fn consume_command(
+    db: &mut Db,
+    state: &mut NotebookState,
+    event: Event,
+) -> Result<NotebookTransition> {
+    use Event::*;
+    use NormalModeTransition::*;
+    use NotebookEvent as NE;
+
+    match event {
+        Notebook(NE::RunCommand(line)) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+            // closed along with the command, as a replayed one never has Enter typed into it
+            let transition = run_command(db, state, &line).await?;
+
+            Ok(NotebookTransition::Batch(vec![
+                NotebookTransition::EditingNormalMode(CloseCommand),
+                transition,
+            ]))
+        }
+        Key(KeyEvent::Esc) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+            CloseCommand.into()
+        }
+        event @ Key(_) => Ok(NotebookTransition::Inedible(event)),
+        _ => Err(Error::Wip("todo: Notebook::consume".to_owned())),
+    }
+}
+
+async fn run_command(
+    db: &mut Db,
+    state: &mut NotebookState,
+    line: &str,
+) -> Result<NotebookTransition> {
+    use NormalModeTransition::*;
+
+    if line.trim().is_empty() {
+        return IdleMode.into();
+    }
+
+    let command = match command::parse(line) {
+        Ok(command) => command,
+        Err(message) => return Ok(NotebookTransition::Alert(message)),
+    };
+
+    match command {
+        Command::Write => SaveNote.into(),
+        Command::WriteAll => SaveAllNotes.into(),
+        Command::Quit => QuitTab { force: false }.into(),
+        Command::ForceQuit => QuitTab { force: true }.into(),
+        Command::WriteQuit => tabs::close(db, state).await,
+        Command::Edit(path) => note::open_path(db, state, &path).await,
+        Command::Goto(address) => MoveCursorToAddress(address).into(),
+        Command::NoHighlight => {
+            state.searching = false;
+
+            ClearSearch.into()
+        }
+        Command::Substitute(substitution) => Substitute(substitution).into(),
+        Command::Registers => ShowRegisters.into(),
+    }
+}
+
+async fn consume_record_macro(
+    state: &mut NotebookState,
+    event: Event,
+) -> Result<NotebookTransition> {
+    use Event::*;
+    use NormalModeTransition::*;
+
+    state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+    match event {
+        Key(KeyEvent::Char(c)) if is_macro_register(c) => {
+            state.macros.start(c);
+
+            StartRecording(c.to_ascii_lowercase()).into()
+        }
+        Key(_) => IdleMode.into(),
+        _ => Err(Error::Wip("todo: Notebook::consume".to_owned())),
+    }
+}
+
+/// Register names are taken before this, as `@{register}` expands into the recorded events.
+async fn consume_play_macro(state: &mut NotebookState, event: Event) -> Result<NotebookTransition> {
+    use Event::*;
+    use NormalModeTransition::*;
+
+    state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+    match event {
+        Key(_) => IdleMode.into(),
+        _ => Err(Error::Wip("todo: Notebook::consume".to_owned())),
+    }
+}
+
+/// Lowercase marks belong to the note, uppercase ones to the notebook.
+async fn consume_mark(state: &mut NotebookState, event: Event) -> Result<NotebookTransition> {
+    use Event::*;
+    use NormalModeTransition::*;
+
+    state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+    match event {
+        Key(KeyEvent::Char(c)) if c.is_ascii_alphabetic() => SetMark(c).into(),
+        Key(_) => IdleMode.into(),
+        _ => Err(Error::Wip("todo: Notebook::consume".to_owned())),
+    }
+}
+
+async fn consume_jump_to_mark(
+    state: &mut NotebookState,
+    linewise: bool,
+    event: Event,
+) -> Result<NotebookTransition> {
+    use Event::*;
+    use NormalModeTransition::*;
+
+    state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+    match event {
+        Key(KeyEvent::Char(c)) if c.is_ascii_alphabetic() => JumpToMark(c, linewise).into(),
+        Key(_) => IdleMode.into(),
+        _ => Err(Error::Wip("todo: Notebook::consume".to_owned())),
+    }
+}
+
+async fn consume_find(
+    state: &mut NotebookState,
+    n: usize,
+    kind: FindKind,
+    action: FindAction,
+    event: Event,
+) -> Result<NotebookTransition> {
+    use Event::*;
+    use NormalModeTransition::*;
+
+    match event {
+        Key(KeyEvent::Char(target)) => {
+            let find = CharFind { kind, target };
+            state.last_find = Some(find);
+
+            run_find(state, find, n, action, false)
+        }
+        Key(_) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+            IdleMode.into()
+        }
+        _ => Err(Error::Wip("todo: Notebook::consume".to_owned())),
+    }
+}
+
+fn run_find(
+    state: &mut NotebookState,
+    find: CharFind,
+    n: usize,
+    action: FindAction,
+    repeat: bool,
+) -> Result<NotebookTransition> {
+    use NormalModeTransition::*;
+
+    state.inner_state = match action {
+        FindAction::Change => InnerState::EditingInsertMode,
+        _ => InnerState::EditingNormalMode(VimNormalState::Idle),
+    };
+
+    match action {
+        FindAction::Move => MoveCursorToChar(find, n, repeat).into(),
+        FindAction::Delete | FindAction::Change => DeleteToChar(find, n).into(),
+        FindAction::Yank => YankToChar(find, n).into(),
+        FindAction::Case(kind) => CaseToChar(kind, find, n).into(),
+    }
+}
+
+/// `;` repeats the last find and `,` repeats it in the other direction.
+fn repeat_find(
+    state: &mut NotebookState,
+    n: usize,
+    reverse: bool,
+    action: FindAction,
+) -> Result<NotebookTransition> {
+    match state.last_find {
+        Some(find) if reverse => run_find(state, find.reversed(), n, action, true),
+        Some(find) => run_find(state, find, n, action, true),
+        None => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+            NormalModeTransition::IdleMode.into()
+        }
+    }
+}
+
+/// `"` names the register of the next yank, delete or paste.
+pub(super) fn is_register_name(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '"' | '-' | '_' | '+' | '*')
+}
+
+async fn consume_register(state: &mut NotebookState, event: Event) -> Result<NotebookTransition> {
+    use Event::*;
+    use NormalModeTransition::*;
+
+    state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+    match event {
+        Key(KeyEvent::Char(c)) if is_register_name(c) => SelectRegister(c).into(),
+        Key(_) => IdleMode.into(),
+        _ => Err(Error::Wip("todo: Notebook::consume".to_owned())),
+    }
+}
@@ -0,0 +1,48 @@
+/// What `~` and `g~` do to the case of letters, or `gu` and `gU`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CaseKind {
+    /// `~` and `g~`
+    Toggle,
+    /// `gu`
+    Lower,
+    /// `gU`
+    Upper,
+}
+
+impl CaseKind {
+    pub(crate) fn from_key(c: char) -> Self {
+        match c {
+            'u' => Self::Lower,
+            'U' => Self::Upper,
+            _ => Self::Toggle,
+        }
+    }
+
+    /// The key after `g`, which typed again works on whole lines as in `guu`.
+    pub(crate) fn key(&self) -> char {
+        match self {
+            Self::Toggle => '~',
+            Self::Lower => 'u',
+            Self::Upper => 'U',
+        }
+    }
+
+    pub fn apply(&self, text: &str) -> String {
+        match self {
+            Self::Lower => text.to_lowercase(),
+            Self::Upper => text.to_uppercase(),
+            Self::Toggle => {
+                let mut toggled = String::with_capacity(text.len());
+                for c in text.chars() {
+                    if c.is_uppercase() {
+                        toggled.extend(c.to_lowercase());
+                    } else {
+                        toggled.extend(c.to_uppercase());
+                    }
+                }
+
+                toggled
+            }
+        }
+    }
+}
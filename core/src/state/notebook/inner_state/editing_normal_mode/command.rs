@@ -0,0 +1,375 @@
+use std::cmp::{max, min};
+
+pub enum Command {
+    Write,
+    WriteAll,
+    Quit,
+    /// `:q!`, which drops the changes of the tab.
+    ForceQuit,
+    /// `:wq` and `:x`, which save the tab before closing it.
+    WriteQuit,
+    Edit(String),
+    Goto(Address),
+    NoHighlight,
+    Substitute(Substitution),
+    Registers,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AddressBase {
+    /// 1-based, as typed.
+    Line(usize),
+    Current,
+    Last,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Address {
+    pub base: AddressBase,
+    pub offset: isize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LineRange {
+    pub start: Address,
+    pub end: Address,
+}
+
+#[derive(Clone, Debug)]
+pub struct Substitution {
+    pub range: LineRange,
+    /// Empty to reuse the last search pattern.
+    pub pattern: String,
+    pub replacement: String,
+    pub global: bool,
+    pub ignore_case: bool,
+}
+
+impl Address {
+    const CURRENT: Self = Self {
+        base: AddressBase::Current,
+        offset: 0,
+    };
+
+    /// Returns the 0-based row, given the cursor row and the last row of the note.
+    pub fn resolve(&self, current: usize, last: usize) -> usize {
+        let base = match self.base {
+            AddressBase::Line(n) => isize::try_from(n).unwrap_or(isize::MAX) - 1,
+            AddressBase::Current => current as isize,
+            AddressBase::Last => last as isize,
+        };
+
+        base.saturating_add(self.offset).clamp(0, last as isize) as usize
+    }
+}
+
+impl LineRange {
+    /// Backwards ranges are swapped, as Vim does after asking.
+    pub fn resolve(&self, current: usize, last: usize) -> (usize, usize) {
+        let start = self.start.resolve(current, last);
+        let end = self.end.resolve(current, last);
+
+        (min(start, end), max(start, end))
+    }
+}
+
+pub fn parse(line: &str) -> Result<Command, String> {
+    let line = line.trim_start_matches([':', ' ']);
+    let (range, rest) = parse_range(line);
+    let rest = rest.trim_start();
+
+    let name_len = rest
+        .find(|c: char| !c.is_ascii_alphabetic())
+        .unwrap_or(rest.len());
+    let (name, args) = rest.split_at(name_len);
+
+    let no_range = |command| match range {
+        Some(_) => Err("No range allowed".to_owned()),
+        None => Ok(command),
+    };
+
+    match (name, args.trim()) {
+        ("", "") => match range {
+            Some(range) => Ok(Command::Goto(range.end)),
+            None => Err("Not an editor command".to_owned()),
+        },
+        ("w" | "write", "") => no_range(Command::Write),
+        ("wa" | "wall", "") => no_range(Command::WriteAll),
+        ("q" | "quit", "") => no_range(Command::Quit),
+        ("q" | "quit", "!") if args == "!" => no_range(Command::ForceQuit),
+        ("wq" | "x" | "xit", "") => no_range(Command::WriteQuit),
+        ("e" | "edit", "") => Err("Argument required".to_owned()),
+        ("e" | "edit", path) if args.starts_with(' ') => no_range(Command::Edit(path.to_owned())),
+        ("noh" | "nohlsearch", "") => no_range(Command::NoHighlight),
+        ("reg" | "registers" | "di" | "display", "") => no_range(Command::Registers),
+        ("s" | "substitute", _) => {
+            let range = range.unwrap_or(LineRange {
+                start: Address::CURRENT,
+                end: Address::CURRENT,
+            });
+
+            parse_substitution(range, args).map(Command::Substitute)
+        }
+        _ => Err(format!("Not an editor command: {line}")),
+    }
+}
+
+fn parse_range(line: &str) -> (Option<LineRange>, &str) {
+    if let Some(rest) = line.strip_prefix('%') {
+        let range = LineRange {
+            start: Address {
+                base: AddressBase::Line(1),
+                offset: 0,
+            },
+            end: Address {
+                base: AddressBase::Last,
+                offset: 0,
+            },
+        };
+
+        return (Some(range), rest);
+    }
+
+    let (start, rest) = match parse_address(line) {
+        (Some(start), rest) => (start, rest),
+        (None, rest) => return (None, rest),
+    };
+
+    match rest.strip_prefix(',') {
+        Some(rest) => {
+            let (end, rest) = parse_address(rest);
+            let end = end.unwrap_or(Address::CURRENT);
+
+            (Some(LineRange { start, end }), rest)
+        }
+        None => (Some(LineRange { start, end: start }), rest),
+    }
+}
+
+fn parse_address(line: &str) -> (Option<Address>, &str) {
+    let (base, mut rest) = match line.chars().next() {
+        Some('.') => (Some(AddressBase::Current), &line[1..]),
+        Some('$') => (Some(AddressBase::Last), &line[1..]),
+        Some(c) if c.is_ascii_digit() => {
+            let (n, rest) = parse_number(line);
+            (Some(AddressBase::Line(n)), rest)
+        }
+        _ => (None, line),
+    };
+
+    let mut offset: isize = 0;
+    while let Some(sign) = rest.chars().next().filter(|c| matches!(c, '+' | '-')) {
+        let (n, tail) = match parse_number(&rest[1..]) {
+            (0, tail) if tail.len() == rest.len() - 1 => (1, tail),
+            (n, tail) => (n, tail),
+        };
+
+        let n = isize::try_from(n).unwrap_or(isize::MAX);
+        offset = if sign == '+' {
+            offset.saturating_add(n)
+        } else {
+            offset.saturating_sub(n)
+        };
+        rest = tail;
+    }
+
+    match (base, offset) {
+        (None, 0) if rest.len() == line.len() => (None, line),
+        (base, offset) => {
+            let base = base.unwrap_or(AddressBase::Current);
+
+            (Some(Address { base, offset }), rest)
+        }
+    }
+}
+
+fn parse_number(line: &str) -> (usize, &str) {
+    let len = line
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(line.len());
+    // digits alone only fail to parse past `usize::MAX`
+    let n = match len {
+        0 => 0,
+        _ => line[..len].parse().unwrap_or(usize::MAX),
+    };
+
+    (n, &line[len..])
+}
+
+/// Parses `/pat/rep/flags`, where any punctuation can be used in place of `/`.
+fn parse_substitution(range: LineRange, args: &str) -> Result<Substitution, String> {
+    let mut chars = args.chars();
+    let delimiter = match chars.next() {
+        Some(c) if c.is_ascii_punctuation() && c != '\\' && c != '"' => c,
+        _ => return Err("Usage: :s/pattern/replacement/flags".to_owned()),
+    };
+
+    let mut parts = Vec::new();
+    let mut part = String::new();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some(c) if c == delimiter => part.push(c),
+                Some(c) => {
+                    part.push('\\');
+                    part.push(c);
+                }
+                None => part.push('\\'),
+            },
+            c if c == delimiter && parts.len() < 2 => parts.push(std::mem::take(&mut part)),
+            c => part.push(c),
+        }
+    }
+    parts.push(part);
+
+    let mut parts = parts.into_iter();
+    let pattern = parts.next().unwrap_or_default();
+    let replacement = parts.next().unwrap_or_default();
+    let flags = parts.next().unwrap_or_default();
+
+    let mut global = false;
+    let mut ignore_case = false;
+    for flag in flags.trim().chars() {
+        match flag {
+            'g' => global = true,
+            'i' => ignore_case = true,
+            'I' => ignore_case = false,
+            _ => return Err(format!("Invalid substitute flag: {flag}")),
+        }
+    }
+
+    Ok(Substitution {
+        range,
+        pattern,
+        replacement,
+        global,
+        ignore_case,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn address(line: &str) -> Address {
+        match parse(line) {
+            Ok(Command::Goto(address)) => address,
+            _ => panic!("not an address: {line}"),
+        }
+    }
+
+    fn substitution(line: &str) -> Substitution {
+        match parse(line) {
+            Ok(Command::Substitute(substitution)) => substitution,
+            _ => panic!("not a substitution: {line}"),
+        }
+    }
+
+    fn error(line: &str) -> String {
+        match parse(line) {
+            Err(error) => error,
+            Ok(_) => panic!("parsed: {line}"),
+        }
+    }
+
+    #[test]
+    fn commands() {
+        assert!(matches!(parse(":w"), Ok(Command::Write)));
+        assert!(matches!(parse(":write"), Ok(Command::Write)));
+        assert!(matches!(parse(":wa"), Ok(Command::WriteAll)));
+        assert!(matches!(parse(":q"), Ok(Command::Quit)));
+        assert!(matches!(parse(":q!"), Ok(Command::ForceQuit)));
+        assert!(matches!(parse(":wq"), Ok(Command::WriteQuit)));
+        assert!(matches!(parse(":x"), Ok(Command::WriteQuit)));
+        assert!(matches!(parse(":noh"), Ok(Command::NoHighlight)));
+        assert!(matches!(parse(":reg"), Ok(Command::Registers)));
+        assert!(
+            matches!(parse(":e notes/todo.md"), Ok(Command::Edit(path)) if path == "notes/todo.md")
+        );
+    }
+
+    #[test]
+    fn line_addresses() {
+        let line = |n, offset| Address {
+            base: AddressBase::Line(n),
+            offset,
+        };
+
+        assert_eq!(address(":42"), line(42, 0));
+        assert_eq!(
+            address(":.+3"),
+            Address {
+                base: AddressBase::Current,
+                offset: 3,
+            }
+        );
+        assert_eq!(
+            address(":$-1"),
+            Address {
+                base: AddressBase::Last,
+                offset: -1,
+            }
+        );
+        assert_eq!(address(":3,7"), line(7, 0));
+        assert_eq!(address(":42").resolve(0, 10), 10);
+        assert_eq!(address(":$-1").resolve(0, 10), 9);
+    }
+
+    #[test]
+    fn substitutions() {
+        let all = substitution(":%s/a/b/g");
+        assert_eq!(all.range.resolve(4, 10), (0, 10));
+        assert_eq!((all.pattern.as_str(), all.replacement.as_str()), ("a", "b"));
+        assert!(all.global && !all.ignore_case);
+
+        let current = substitution(":s#a/b#c\\#d#i");
+        assert_eq!(current.range.resolve(4, 10), (4, 4));
+        assert_eq!(current.pattern, "a/b");
+        assert_eq!(current.replacement, "c#d");
+        assert!(!current.global && current.ignore_case);
+
+        let reused = substitution(":.,$s//x/");
+        assert_eq!(reused.range.resolve(4, 10), (4, 10));
+        assert!(reused.pattern.is_empty());
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(error(":"), "Not an editor command");
+        assert_eq!(error(":3w"), "No range allowed");
+        assert_eq!(error(":e"), "Argument required");
+        assert_eq!(error(":s"), "Usage: :s/pattern/replacement/flags");
+        assert_eq!(error(":s\\a\\b\\"), "Usage: :s/pattern/replacement/flags");
+        assert_eq!(error(":s/a/b/x"), "Invalid substitute flag: x");
+        assert_eq!(error(":foo"), "Not an editor command: foo");
+        assert_eq!(error(":q !"), "Not an editor command: q !");
+        assert_eq!(error(":edit"), "Argument required");
+    }
+
+    #[test]
+    fn huge_offsets_saturate() {
+        let current = |offset| Address {
+            base: AddressBase::Current,
+            offset,
+        };
+
+        assert_eq!(address(":.+9223372036854775807+1"), current(isize::MAX));
+        assert_eq!(address(":+9999999999999999999"), current(isize::MAX));
+        assert_eq!(address(":-99999999999999999999999"), current(-isize::MAX));
+        assert_eq!(address(":.-9223372036854775807-5"), current(isize::MIN));
+        assert_eq!(address(":+9999999999999999999").resolve(3, 10), 10);
+        assert_eq!(address(":-9999999999999999999").resolve(3, 10), 0);
+    }
+
+    #[test]
+    fn chained_offsets_add_up() {
+        let current = |offset| Address {
+            base: AddressBase::Current,
+            offset,
+        };
+
+        assert_eq!(address(":.+3-1+2"), current(4));
+        assert_eq!(address(":++-"), current(1));
+        assert_eq!(address(":+2+"), current(3));
+    }
+}
@@ -0,0 +1,60 @@
+use super::CaseKind;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FindKind {
+    /// `f`
+    Find,
+    /// `t`
+    Till,
+    /// `F`
+    FindBack,
+    /// `T`
+    TillBack,
+}
+
+/// A character search within the cursor line, kept for `;` and `,`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CharFind {
+    pub kind: FindKind,
+    pub target: char,
+}
+
+/// What a pending find or text object does once its last key is typed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FindAction {
+    Move,
+    Delete,
+    Change,
+    Yank,
+    /// `g~`, `gu` or `gU`
+    Case(CaseKind),
+}
+
+impl FindKind {
+    pub(crate) fn from_key(c: char) -> Self {
+        match c {
+            'f' => Self::Find,
+            't' => Self::Till,
+            'F' => Self::FindBack,
+            _ => Self::TillBack,
+        }
+    }
+
+    pub fn is_backward(&self) -> bool {
+        matches!(self, Self::FindBack | Self::TillBack)
+    }
+}
+
+impl CharFind {
+    /// The same search in the other direction, used by `,`.
+    pub fn reversed(self) -> Self {
+        let kind = match self.kind {
+            FindKind::Find => FindKind::FindBack,
+            FindKind::Till => FindKind::TillBack,
+            FindKind::FindBack => FindKind::Find,
+            FindKind::TillBack => FindKind::Till,
+        };
+
+        Self { kind, ..self }
+    }
+}
@@ -0,0 +1,53 @@
+/// A motion which operators take as well, such as `d}`, `y%` or `gUL`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Motion {
+    /// `}`, to the blank line after the paragraph
+    ParagraphForward,
+    /// `{`
+    ParagraphBack,
+    /// `)`, to the start of the next sentence
+    SentenceForward,
+    /// `(`
+    SentenceBack,
+    /// `%`, to the bracket matching the one under or after the cursor
+    MatchBracket,
+    /// `{count}%`, to the line that many percent into the note
+    Percent,
+    /// `H`, `{count}H` counting down from the top of the screen
+    ScreenTop,
+    /// `M`
+    ScreenMiddle,
+    /// `L`, `{count}L` counting up from the bottom of the screen
+    ScreenBottom,
+}
+
+impl Motion {
+    pub(crate) fn from_key(c: char) -> Self {
+        match c {
+            '}' => Self::ParagraphForward,
+            '{' => Self::ParagraphBack,
+            ')' => Self::SentenceForward,
+            '(' => Self::SentenceBack,
+            'H' => Self::ScreenTop,
+            'M' => Self::ScreenMiddle,
+            'L' => Self::ScreenBottom,
+            _ => Self::MatchBracket,
+        }
+    }
+
+    /// `%` with a count goes to the line that percent into the note, not the matching bracket.
+    pub(crate) fn from_counted_key(c: char, counted: bool) -> Self {
+        match c {
+            '%' if counted => Self::Percent,
+            c => Self::from_key(c),
+        }
+    }
+
+    /// Whether an operator takes the whole lines between the cursor and where it moves to.
+    pub fn is_linewise(&self) -> bool {
+        matches!(
+            self,
+            Self::Percent | Self::ScreenTop | Self::ScreenMiddle | Self::ScreenBottom
+        )
+    }
+}
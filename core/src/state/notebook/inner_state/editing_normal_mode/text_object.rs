@@ -0,0 +1,57 @@
+/// `i` takes the content of a text object, `a` its surroundings as well.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ObjectScope {
+    Inner,
+    Around,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ObjectKind {
+    /// `w`
+    Word,
+    /// `W`, a run of non-blank characters
+    BigWord,
+    /// `s`
+    Sentence,
+    /// `p`, taken as whole lines
+    Paragraph,
+    /// `"`, `'` or `` ` `` within the cursor line
+    Quote(char),
+    /// `(`, `[`, `{` or `<` with its closing bracket
+    Bracket(char, char),
+}
+
+/// What `i` or `a` followed by an object key stands for, such as `i(` or `aw`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TextObject {
+    pub scope: ObjectScope,
+    pub kind: ObjectKind,
+}
+
+impl ObjectScope {
+    pub(crate) fn from_key(c: char) -> Self {
+        match c {
+            'i' => Self::Inner,
+            _ => Self::Around,
+        }
+    }
+}
+
+impl ObjectKind {
+    pub(crate) fn from_key(c: char) -> Option<Self> {
+        let kind = match c {
+            'w' => Self::Word,
+            'W' => Self::BigWord,
+            's' => Self::Sentence,
+            'p' => Self::Paragraph,
+            '"' | '\'' | '`' => Self::Quote(c),
+            '(' | ')' | 'b' => Self::Bracket('(', ')'),
+            '[' | ']' => Self::Bracket('[', ']'),
+            '{' | '}' | 'B' => Self::Bracket('{', '}'),
+            '<' | '>' => Self::Bracket('<', '>'),
+            _ => return None,
+        };
+
+        Some(kind)
+    }
+}
@@ -0,0 +1,24 @@
+use crate::{
+    db::Db,
+    state::notebook::{note, NotebookState},
+    transition::InsertModeTransition,
+    Error, Event, KeyEvent, NotebookEvent, NotebookTransition, Result,
+};
+
+/// `R`, where typed characters take the place of those under the cursor.
+pub async fn consume(
+    _db: &mut Db,
+    state: &mut NotebookState,
+    event: Event,
+) -> Result<NotebookTransition> {
+    use Event::*;
+    use NotebookEvent::*;
+
+    match event {
+        Key(KeyEvent::Esc) | Notebook(ViewNote) => note::view(state).await,
+        Key(key) => Ok(NotebookTransition::EditingInsertMode(
+            InsertModeTransition::Overwrite(key),
+        )),
+        _ => Err(Error::Wip("todo: Notebook::consume".to_owned())),
+    }
+}
@@ -1,6 +1,9 @@
 use crate::{
     db::Db,
-    state::notebook::{InnerState, NotebookState, VimNormalState},
+    state::notebook::{
+        inner_state::editing_normal_mode::is_register_name, CharFind, FindKind, InnerState, Motion,
+        NotebookState, ObjectKind, ObjectScope, TextObject, VimNormalState,
+    },
     transition::{NormalModeTransition, NotebookTransition, VimKeymapKind, VisualModeTransition},
     Error, Event, KeyEvent, NumKey, Result,
 };
@@ -10,6 +13,29 @@ pub enum VimVisualState {
     Idle,
     Gateway,
     Numbering(usize),
+    Register,
+    Find(usize, FindKind),
+    TextObject(usize, ObjectScope),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum VisualKind {
+    /// `v`
+    Charwise,
+    /// `V`
+    Linewise,
+    /// `Ctrl+v`
+    Blockwise,
+}
+
+impl VisualKind {
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Charwise => "visual",
+            Self::Linewise => "visual line",
+            Self::Blockwise => "visual block",
+        }
+    }
 }
 
 pub async fn consume(
@@ -22,6 +48,9 @@ pub async fn consume(
         VimVisualState::Idle => consume_idle(db, state, event).await,
         VimVisualState::Gateway => consume_gateway(db, state, event).await,
         VimVisualState::Numbering(n) => consume_numbering(db, state, n, event).await,
+        VimVisualState::Register => consume_register(state, event).await,
+        VimVisualState::Find(n, kind) => consume_find(state, n, kind, event).await,
+        VimVisualState::TextObject(n, scope) => consume_text_object(state, n, scope, event).await,
     }
 }
 
@@ -34,38 +63,88 @@ async fn consume_idle(
     use VisualModeTransition::*;
 
     match event {
-        Key(KeyEvent::J | KeyEvent::Down) => MoveCursorDown(1).into(),
-        Key(KeyEvent::K | KeyEvent::Up) => MoveCursorUp(1).into(),
-        Key(KeyEvent::H | KeyEvent::Left) => MoveCursorBack(1).into(),
-        Key(KeyEvent::L | KeyEvent::Right) => MoveCursorForward(1).into(),
-        Key(KeyEvent::W) => MoveCursorWordForward(1).into(),
-        Key(KeyEvent::E) => MoveCursorWordEnd(1).into(),
-        Key(KeyEvent::B) => MoveCursorWordBack(1).into(),
-        Key(KeyEvent::Num(NumKey::Zero)) => MoveCursorLineStart.into(),
-        Key(KeyEvent::DollarSign) => MoveCursorLineEnd.into(),
-        Key(KeyEvent::Caret) => MoveCursorLineNonEmptyStart.into(),
-        Key(KeyEvent::CapG) => MoveCursorBottom.into(),
-        Key(KeyEvent::D | KeyEvent::X) => {
+        Key(KeyEvent::Char('j') | KeyEvent::Down) => MoveCursorDown(1).into(),
+        Key(KeyEvent::Char('k') | KeyEvent::Up) => MoveCursorUp(1).into(),
+        Key(KeyEvent::Char('h') | KeyEvent::Left) => MoveCursorBack(1).into(),
+        Key(KeyEvent::Char('l') | KeyEvent::Right) => MoveCursorForward(1).into(),
+        Key(KeyEvent::Char('w')) => MoveCursorWordForward(1).into(),
+        Key(KeyEvent::Char('e')) => MoveCursorWordEnd(1).into(),
+        Key(KeyEvent::Char('b')) => MoveCursorWordBack(1).into(),
+        Key(KeyEvent::Char('0')) => MoveCursorLineStart.into(),
+        Key(KeyEvent::Char('$')) => MoveCursorLineEnd.into(),
+        Key(KeyEvent::Char('^')) => MoveCursorLineNonEmptyStart.into(),
+        Key(KeyEvent::Char('G')) => MoveCursorBottom.into(),
+        Key(KeyEvent::Char(c @ ('{' | '}' | '(' | ')' | '%' | 'H' | 'M' | 'L'))) => {
+            MoveCursor(Motion::from_key(c), 1).into()
+        }
+        Key(KeyEvent::Ctrl('d')) => ScrollHalfPageDown(1).into(),
+        Key(KeyEvent::Ctrl('u')) => ScrollHalfPageUp(1).into(),
+        Key(KeyEvent::Ctrl('f')) => ScrollPageDown(1).into(),
+        Key(KeyEvent::Ctrl('b')) => ScrollPageUp(1).into(),
+        Key(KeyEvent::Char(c @ ('f' | 't' | 'F' | 'T'))) => {
+            let kind = FindKind::from_key(c);
+            state.inner_state = InnerState::EditingVisualMode(VimVisualState::Find(1, kind));
+
+            FindMode.into()
+        }
+        Key(KeyEvent::Char(c @ (';' | ','))) => {
+            state.inner_state = InnerState::EditingVisualMode(VimVisualState::Idle);
+
+            repeat_find(state, 1, c == ',')
+        }
+        Key(KeyEvent::Char(c @ ('i' | 'a'))) => {
+            let scope = ObjectScope::from_key(c);
+            state.inner_state = InnerState::EditingVisualMode(VimVisualState::TextObject(1, scope));
+
+            TextObjectMode.into()
+        }
+        Key(KeyEvent::Char('d' | 'x')) => {
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
 
             DeleteSelection.into()
         }
 
-        Key(KeyEvent::S | KeyEvent::CapS) => {
+        Key(KeyEvent::Char('s' | 'S' | 'c')) => {
             state.inner_state = InnerState::EditingInsertMode;
 
             DeleteSelectionAndInsertMode.into()
         }
-        Key(KeyEvent::Y) => {
+        Key(KeyEvent::Char(c @ ('I' | 'A'))) if state.visual_kind == VisualKind::Blockwise => {
+            state.inner_state = InnerState::EditingInsertMode;
+
+            match c {
+                'I' => InsertBlock.into(),
+                _ => AppendBlock.into(),
+            }
+        }
+        Key(KeyEvent::Char('v')) => switch_kind(state, VisualKind::Charwise),
+        Key(KeyEvent::Char('V')) => switch_kind(state, VisualKind::Linewise),
+        Key(KeyEvent::Ctrl('v')) => switch_kind(state, VisualKind::Blockwise),
+        Key(KeyEvent::Char('y')) => {
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
 
             YankSelection.into()
         }
-        Key(KeyEvent::G) => {
+        Key(KeyEvent::Char('>')) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+            IndentSelection(1).into()
+        }
+        Key(KeyEvent::Char('<')) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+            OutdentSelection(1).into()
+        }
+        Key(KeyEvent::Char('g')) => {
             state.inner_state = InnerState::EditingVisualMode(VimVisualState::Gateway);
 
             GatewayMode.into()
         }
+        Key(KeyEvent::Char('"')) => {
+            state.inner_state = InnerState::EditingVisualMode(VimVisualState::Register);
+
+            RegisterMode.into()
+        }
         Key(KeyEvent::Esc) => {
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
 
@@ -73,12 +152,15 @@ async fn consume_idle(
                 NormalModeTransition::IdleMode,
             ))
         }
-        Key(KeyEvent::Num(n)) => {
+        Key(KeyEvent::Char(c @ '0'..='9')) => {
+            let n = NumKey::try_from(c)?;
             state.inner_state = InnerState::EditingVisualMode(VimVisualState::Numbering(n.into()));
 
             NumberingMode.into()
         }
-        Key(KeyEvent::CtrlH) => Ok(NotebookTransition::ShowVimKeymap(VimKeymapKind::VisualIdle)),
+        Key(KeyEvent::Ctrl('h')) => {
+            Ok(NotebookTransition::ShowVimKeymap(VimKeymapKind::VisualIdle))
+        }
         event @ Key(_) => Ok(NotebookTransition::Inedible(event)),
         _ => Err(Error::Wip("todo: Notebook::consume".to_owned())),
     }
@@ -93,7 +175,7 @@ async fn consume_gateway(
     use VisualModeTransition::*;
 
     match event {
-        Key(KeyEvent::G) => {
+        Key(KeyEvent::Char('g')) => {
             state.inner_state = InnerState::EditingVisualMode(VimVisualState::Idle);
 
             MoveCursorTop.into()
@@ -122,52 +204,110 @@ async fn consume_numbering(
     use VisualModeTransition::*;
 
     match event {
-        Key(KeyEvent::Num(n2)) => {
+        Key(KeyEvent::Char(c @ '0'..='9')) => {
+            let n2 = NumKey::try_from(c)?;
             let step = n2 + n.saturating_mul(10);
             state.inner_state = InnerState::EditingVisualMode(VimVisualState::Numbering(step));
 
             Ok(NotebookTransition::None)
         }
-        Key(KeyEvent::J | KeyEvent::Down) => {
+        Key(KeyEvent::Char('j') | KeyEvent::Down) => {
             state.inner_state = InnerState::EditingVisualMode(VimVisualState::Idle);
 
             MoveCursorDown(n).into()
         }
-        Key(KeyEvent::K | KeyEvent::Up) => {
+        Key(KeyEvent::Char('k') | KeyEvent::Up) => {
             state.inner_state = InnerState::EditingVisualMode(VimVisualState::Idle);
 
             MoveCursorUp(n).into()
         }
-        Key(KeyEvent::H | KeyEvent::Left) => {
+        Key(KeyEvent::Char('h') | KeyEvent::Left) => {
             state.inner_state = InnerState::EditingVisualMode(VimVisualState::Idle);
 
             NormalModeTransition::MoveCursorBack(n).into()
         }
-        Key(KeyEvent::L | KeyEvent::Right) => {
+        Key(KeyEvent::Char('l') | KeyEvent::Right) => {
             state.inner_state = InnerState::EditingVisualMode(VimVisualState::Idle);
 
             MoveCursorForward(n).into()
         }
-        Key(KeyEvent::W) => {
+        Key(KeyEvent::Char('w')) => {
             state.inner_state = InnerState::EditingVisualMode(VimVisualState::Idle);
 
             MoveCursorWordForward(n).into()
         }
-        Key(KeyEvent::E) => {
+        Key(KeyEvent::Char('e')) => {
             state.inner_state = InnerState::EditingVisualMode(VimVisualState::Idle);
 
             MoveCursorWordEnd(n).into()
         }
-        Key(KeyEvent::B) => {
+        Key(KeyEvent::Char('b')) => {
             state.inner_state = InnerState::EditingVisualMode(VimVisualState::Idle);
 
             MoveCursorWordBack(n).into()
         }
-        Key(KeyEvent::CapG) => {
+        Key(KeyEvent::Char('G')) => {
             state.inner_state = InnerState::EditingVisualMode(VimVisualState::Idle);
 
             MoveCursorToLine(n).into()
         }
+        Key(KeyEvent::Char('%')) => {
+            state.inner_state = InnerState::EditingVisualMode(VimVisualState::Idle);
+
+            MoveCursor(Motion::Percent, n).into()
+        }
+        Key(KeyEvent::Char(c @ ('{' | '}' | '(' | ')' | 'H' | 'M' | 'L'))) => {
+            state.inner_state = InnerState::EditingVisualMode(VimVisualState::Idle);
+
+            MoveCursor(Motion::from_key(c), n).into()
+        }
+        Key(KeyEvent::Ctrl('d')) => {
+            state.inner_state = InnerState::EditingVisualMode(VimVisualState::Idle);
+
+            ScrollHalfPageDown(n).into()
+        }
+        Key(KeyEvent::Ctrl('u')) => {
+            state.inner_state = InnerState::EditingVisualMode(VimVisualState::Idle);
+
+            ScrollHalfPageUp(n).into()
+        }
+        Key(KeyEvent::Ctrl('f')) => {
+            state.inner_state = InnerState::EditingVisualMode(VimVisualState::Idle);
+
+            ScrollPageDown(n).into()
+        }
+        Key(KeyEvent::Ctrl('b')) => {
+            state.inner_state = InnerState::EditingVisualMode(VimVisualState::Idle);
+
+            ScrollPageUp(n).into()
+        }
+        Key(KeyEvent::Char(c @ ('f' | 't' | 'F' | 'T'))) => {
+            let kind = FindKind::from_key(c);
+            state.inner_state = InnerState::EditingVisualMode(VimVisualState::Find(n, kind));
+
+            FindMode.into()
+        }
+        Key(KeyEvent::Char(c @ (';' | ','))) => {
+            state.inner_state = InnerState::EditingVisualMode(VimVisualState::Idle);
+
+            repeat_find(state, n, c == ',')
+        }
+        Key(KeyEvent::Char(c @ ('i' | 'a'))) => {
+            let scope = ObjectScope::from_key(c);
+            state.inner_state = InnerState::EditingVisualMode(VimVisualState::TextObject(n, scope));
+
+            TextObjectMode.into()
+        }
+        Key(KeyEvent::Char('>')) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+            IndentSelection(n).into()
+        }
+        Key(KeyEvent::Char('<')) => {
+            state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+            OutdentSelection(n).into()
+        }
         Key(KeyEvent::Esc) => {
             state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
 
@@ -175,7 +315,7 @@ async fn consume_numbering(
                 NormalModeTransition::IdleMode,
             ))
         }
-        Key(KeyEvent::CtrlH) => Ok(NotebookTransition::ShowVimKeymap(
+        Key(KeyEvent::Ctrl('h')) => Ok(NotebookTransition::ShowVimKeymap(
             VimKeymapKind::VisualNumbering,
         )),
         event @ Key(_) => {
@@ -192,3 +332,93 @@ impl From<VisualModeTransition> for Result<NotebookTransition> {
         Ok(NotebookTransition::EditingVisualMode(transition))
     }
 }
+
+async fn consume_register(state: &mut NotebookState, event: Event) -> Result<NotebookTransition> {
+    use Event::*;
+    use VisualModeTransition::*;
+
+    state.inner_state = InnerState::EditingVisualMode(VimVisualState::Idle);
+
+    match event {
+        Key(KeyEvent::Char(c)) if is_register_name(c) => SelectRegister(c).into(),
+        Key(_) => Ok(NotebookTransition::None),
+        _ => Err(Error::Wip("todo: Notebook::consume".to_owned())),
+    }
+}
+
+async fn consume_find(
+    state: &mut NotebookState,
+    n: usize,
+    kind: FindKind,
+    event: Event,
+) -> Result<NotebookTransition> {
+    use Event::*;
+    use VisualModeTransition::*;
+
+    state.inner_state = InnerState::EditingVisualMode(VimVisualState::Idle);
+
+    match event {
+        Key(KeyEvent::Char(target)) => {
+            let find = CharFind { kind, target };
+            state.last_find = Some(find);
+
+            MoveCursorToChar(find, n, false).into()
+        }
+        Key(_) => Ok(NotebookTransition::None),
+        _ => Err(Error::Wip("todo: Notebook::consume".to_owned())),
+    }
+}
+
+/// Selects the object, which is charwise unless it is a paragraph.
+async fn consume_text_object(
+    state: &mut NotebookState,
+    n: usize,
+    scope: ObjectScope,
+    event: Event,
+) -> Result<NotebookTransition> {
+    use Event::*;
+    use VisualModeTransition::*;
+
+    state.inner_state = InnerState::EditingVisualMode(VimVisualState::Idle);
+
+    match event {
+        Key(KeyEvent::Char(c)) => match ObjectKind::from_key(c) {
+            Some(kind) => {
+                state.visual_kind = match kind {
+                    ObjectKind::Paragraph => VisualKind::Linewise,
+                    _ => VisualKind::Charwise,
+                };
+
+                SelectTextObject(TextObject { scope, kind }, state.visual_kind, n).into()
+            }
+            None => Ok(NotebookTransition::None),
+        },
+        Key(_) => Ok(NotebookTransition::None),
+        _ => Err(Error::Wip("todo: Notebook::consume".to_owned())),
+    }
+}
+
+/// Typing the key of the current kind leaves visual mode, as in Vim.
+fn switch_kind(state: &mut NotebookState, kind: VisualKind) -> Result<NotebookTransition> {
+    if state.visual_kind == kind {
+        state.inner_state = InnerState::EditingNormalMode(VimNormalState::Idle);
+
+        return Ok(NotebookTransition::EditingNormalMode(
+            NormalModeTransition::IdleMode,
+        ));
+    }
+
+    state.visual_kind = kind;
+
+    VisualModeTransition::ChangeKind(kind).into()
+}
+
+fn repeat_find(state: &NotebookState, n: usize, reverse: bool) -> Result<NotebookTransition> {
+    match state.last_find {
+        Some(find) if reverse => {
+            VisualModeTransition::MoveCursorToChar(find.reversed(), n, true).into()
+        }
+        Some(find) => VisualModeTransition::MoveCursorToChar(find, n, false).into(),
+        None => Ok(NotebookTransition::None),
+    }
+}
@@ -0,0 +1,78 @@
+use {
+    super::REPLAY_LIMIT,
+    crate::{Event, KeyEvent},
+    std::collections::HashMap,
+};
+
+/// Events recorded with `q{register}` and replayed with `@{register}`.
+#[derive(Default)]
+pub struct Macros {
+    recording: Option<char>,
+    events: Vec<Event>,
+    registers: HashMap<char, Vec<Event>>,
+    /// Run again by `@@`.
+    last: Option<char>,
+}
+
+/// Uppercase names append to the lowercase register, as in Vim.
+pub(super) fn is_macro_register(c: char) -> bool {
+    c.is_ascii_alphanumeric()
+}
+
+impl Macros {
+    pub fn recording(&self) -> Option<char> {
+        self.recording
+    }
+
+    pub(super) fn start(&mut self, name: char) {
+        let register = name.to_ascii_lowercase();
+        self.events = match name.is_ascii_uppercase() {
+            true => self.registers.remove(&register).unwrap_or_default(),
+            false => Vec::new(),
+        };
+        self.recording = Some(register);
+    }
+
+    /// Keeps what has been recorded, less the `q` which stopped it.
+    pub(super) fn stop(&mut self) -> Option<char> {
+        let register = self.recording.take()?;
+        let mut events = std::mem::take(&mut self.events);
+        if let Some(Event::Key(KeyEvent::Char('q'))) = events.last() {
+            events.pop();
+        }
+
+        self.registers.insert(register, events);
+
+        Some(register)
+    }
+
+    pub(super) fn record(&mut self, event: &Event) {
+        if self.recording.is_some() {
+            self.events.push(event.clone());
+        }
+    }
+
+    /// The events of the register, `count` times over; `@` stands for the last one run.
+    pub(super) fn replay(&mut self, name: char, count: usize) -> Vec<Event> {
+        let register = match name {
+            '@' => match self.last {
+                Some(register) => register,
+                None => return Vec::new(),
+            },
+            name => name.to_ascii_lowercase(),
+        };
+
+        let events = match self.registers.get(&register) {
+            Some(events) => events,
+            None => return Vec::new(),
+        };
+        self.last = Some(register);
+
+        events
+            .iter()
+            .cloned()
+            .cycle()
+            .take(events.len().saturating_mul(count).min(REPLAY_LIMIT + 1))
+            .collect()
+    }
+}
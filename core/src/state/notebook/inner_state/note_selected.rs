@@ -1,7 +1,7 @@
 use crate::{
     db::Db,
     state::notebook::{directory, note, traverse, InnerState, NotebookState},
-    Error, Event, KeyEvent, NotebookEvent, NotebookTransition, Result,
+    Error, Event, KeyEvent, NotebookEvent, NotebookTransition, NumKey, Result,
 };
 
 pub async fn consume(
@@ -26,30 +26,31 @@ pub async fn consume(
 
             directory::close(state, directory)
         }
-        Key(KeyEvent::H) | Key(KeyEvent::Left) => {
+        Key(KeyEvent::Char('h')) | Key(KeyEvent::Left) => {
             let directory_id = &state.get_selected_note()?.directory_id;
             let directory_item = state.root.find(directory_id).ok_or(Error::Wip(
-                "[Key::H] failed to find parent directory".to_owned(),
+                "[Key::h] failed to find parent directory".to_owned(),
             ))?;
             let directory = directory_item.directory.clone();
 
             directory::close(state, directory)
         }
-        Key(KeyEvent::J | KeyEvent::Down) => traverse::select_next(state),
-        Key(KeyEvent::K | KeyEvent::Up) => traverse::select_prev(state),
-        Key(KeyEvent::M) => {
+        Key(KeyEvent::Char('j') | KeyEvent::Down) => traverse::select_next(state),
+        Key(KeyEvent::Char('k') | KeyEvent::Up) => traverse::select_prev(state),
+        Key(KeyEvent::Char('m')) => {
             let note = state.get_selected_note()?.clone();
 
             note::show_actions_dialog(state, note)
         }
         Notebook(SelectNote(note)) => note::select(state, note),
         Notebook(SelectDirectory(directory)) => directory::select(state, directory),
-        Key(KeyEvent::L | KeyEvent::Enter) | Notebook(OpenNote) => {
+        Key(KeyEvent::Char('l') | KeyEvent::Enter) | Notebook(OpenNote) => {
             let note = state.get_selected_note()?.clone();
 
             note::open(db, state, note).await
         }
-        Key(KeyEvent::Num(n)) => {
+        Key(KeyEvent::Char(c @ '0'..='9')) => {
+            let n = NumKey::try_from(c)?;
             state.inner_state = InnerState::NoteTreeNumber(n.into());
 
             Ok(NotebookTransition::None)
@@ -1,7 +1,7 @@
 use crate::{
     db::Db,
     state::notebook::{directory, note, InnerState, NotebookState, SelectedItem},
-    Error, Event, KeyEvent, NotebookEvent, NotebookTransition, Result,
+    Error, Event, KeyEvent, NotebookEvent, NotebookTransition, NumKey, Result,
 };
 
 pub async fn consume(
@@ -28,7 +28,8 @@ pub async fn consume(
     match event {
         Notebook(SelectNote(note)) => note::select(state, note),
         Notebook(SelectDirectory(directory)) => directory::select(state, directory),
-        Key(KeyEvent::Num(n2)) => {
+        Key(KeyEvent::Char(c @ '0'..='9')) => {
+            let n2 = NumKey::try_from(c)?;
             let step = n2 + n.saturating_mul(10);
             state.inner_state = InnerState::NoteTreeNumber(step);
 
@@ -38,11 +39,11 @@ pub async fn consume(
             reset_state(state);
             Ok(NotebookTransition::None)
         }
-        Key(KeyEvent::J | KeyEvent::Down) => {
+        Key(KeyEvent::Char('j') | KeyEvent::Down) => {
             reset_state(state);
             Ok(NotebookTransition::SelectNext(n))
         }
-        Key(KeyEvent::K | KeyEvent::Up) => {
+        Key(KeyEvent::Char('k') | KeyEvent::Up) => {
             reset_state(state);
             Ok(NotebookTransition::SelectPrev(n))
         }
@@ -0,0 +1,137 @@
+use crate::{
+    state::notebook::{InnerState, VimNormalState},
+    Event, KeyEvent, NotebookTransition,
+};
+
+/// Records the events of each normal mode command, including the text typed in
+/// insert mode, so that the last one which changed the note can be replayed by `.`.
+#[derive(Default)]
+pub struct Repeat {
+    events: Vec<Event>,
+    changed: bool,
+    /// Text typed after a change made in visual mode, which `.` does not repeat.
+    skipping: bool,
+    last: Option<Change>,
+}
+
+#[derive(Clone)]
+struct Change {
+    count: Option<usize>,
+    events: Vec<Event>,
+}
+
+impl Repeat {
+    /// Whether events consumed in this state belong to a command being typed.
+    pub(super) fn is_recording(inner_state: &InnerState) -> bool {
+        match inner_state {
+            InnerState::EditingNormalMode(VimNormalState::Search(_) | VimNormalState::Command) => {
+                false
+            }
+            InnerState::EditingNormalMode(_)
+            | InnerState::EditingInsertMode
+            | InnerState::EditingReplaceMode => true,
+            _ => false,
+        }
+    }
+
+    pub(super) fn record(
+        &mut self,
+        event: Event,
+        transition: &NotebookTransition,
+        inner_state: &InnerState,
+    ) {
+        if self.skipping {
+            self.skipping = matches!(inner_state, InnerState::EditingInsertMode);
+            return;
+        }
+
+        self.events.push(event);
+        self.changed |= matches!(
+            transition,
+            NotebookTransition::EditingNormalMode(transition) if transition.is_change()
+        ) || matches!(
+            inner_state,
+            InnerState::EditingInsertMode | InnerState::EditingReplaceMode
+        );
+
+        let pending = match inner_state {
+            InnerState::EditingNormalMode(VimNormalState::Idle) => false,
+            state => Self::is_recording(state),
+        };
+        if pending {
+            return;
+        }
+
+        let events = std::mem::take(&mut self.events);
+        if std::mem::take(&mut self.changed) {
+            self.last = Some(Change::new(events));
+        }
+    }
+
+    /// Drops the command being typed, which `.` or `@` replaces.
+    pub(super) fn discard(&mut self) {
+        self.events.clear();
+        self.changed = false;
+    }
+
+    pub(super) fn skip(&mut self) {
+        self.discard();
+        self.skipping = true;
+    }
+
+    /// The events of the last change for `.`, with `count` in place of its own.
+    /// What has been typed of the current command, such as the count, is dropped.
+    pub(super) fn replay(&mut self, count: Option<usize>) -> Vec<Event> {
+        self.discard();
+
+        match self.last.clone() {
+            Some(change) => Change {
+                count: count.or(change.count),
+                events: change.events,
+            }
+            .into_events(),
+            None => Vec::new(),
+        }
+    }
+}
+
+impl Change {
+    /// Splits the leading count off, so `.` can replace it.
+    fn new(events: Vec<Event>) -> Self {
+        let digits = events
+            .iter()
+            .enumerate()
+            .take_while(|(i, event)| match event {
+                Event::Key(KeyEvent::Char(c)) => c.is_ascii_digit() && (*i > 0 || *c != '0'),
+                _ => false,
+            })
+            .count();
+
+        let count = events[..digits]
+            .iter()
+            .fold(None, |count, event| match event {
+                Event::Key(KeyEvent::Char(c)) => {
+                    let n = c.to_digit(10).unwrap_or_default() as usize;
+
+                    Some(count.unwrap_or(0usize).saturating_mul(10).saturating_add(n))
+                }
+                _ => count,
+            });
+
+        Self {
+            count,
+            events: events[digits..].to_vec(),
+        }
+    }
+
+    /// The events to replay, with `count` typed in front.
+    fn into_events(self) -> Vec<Event> {
+        let count = self.count.map(|n| n.to_string()).unwrap_or_default();
+
+        count
+            .chars()
+            .map(|c| Event::Key(KeyEvent::Char(c)))
+            .chain(self.events)
+            .collect()
+    }
+}
@@ -1,9 +1,12 @@
 use {
     crate::{
         data::{Directory, Note},
-        state::notebook::DirectoryItem,
+        state::notebook::{
+            Address, CaseKind, CharFind, DirectoryItem, Motion, SearchDirection, Substitution,
+            TextObject, VisualKind,
+        },
         types::{DirectoryId, NoteId},
-        Event,
+        Event, KeyEvent,
     },
     strum_macros::Display,
 };
@@ -49,6 +52,7 @@ pub enum NotebookTransition {
     RemoveDirectory {
         directory: Directory,
         selected_directory: Directory,
+        note_ids: Vec<NoteId>,
     },
 
     AddNote(Note),
@@ -69,6 +73,7 @@ pub enum NotebookTransition {
     UpdateNoteContent(NoteId),
 
     Alert(String),
+    CloseNotebook,
 
     #[strum(to_string = "Inedible::{0}")]
     Inedible(Event),
@@ -79,7 +84,11 @@ pub enum NotebookTransition {
     SelectPrev(usize),
     EditingNormalMode(NormalModeTransition),
     EditingVisualMode(VisualModeTransition),
+    EditingInsertMode(InsertModeTransition),
     ShowVimKeymap(VimKeymapKind),
+
+    /// Transitions to be handled in order, such as those of replayed events.
+    Batch(Vec<NotebookTransition>),
 }
 
 #[derive(Clone, Copy, Display)]
@@ -102,12 +111,19 @@ pub enum NormalModeTransition {
     GatewayMode,
     YankMode,
     DeleteMode,
-    DeleteInsideMode,
     ChangeMode,
-    ChangeInsideMode,
+    TextObjectMode,
+    IndentMode,
+    OutdentMode,
+    CaseMode,
+    ReplaceCharMode,
     NextTab(NoteId),
     PrevTab(NoteId),
     CloseTab(NoteId),
+    /// `:q`, which the TUI refuses for a tab with unsaved changes unless `force`d.
+    QuitTab {
+        force: bool,
+    },
     ToggleLineNumbers,
     ToggleBrowser,
     MoveCursorDown(usize),
@@ -123,6 +139,12 @@ pub enum NormalModeTransition {
     MoveCursorTop,
     MoveCursorBottom,
     MoveCursorToLine(usize),
+    MoveCursor(Motion, usize),
+    /// `Ctrl+d`, moving the cursor as far as the view scrolls.
+    ScrollHalfPageDown(usize),
+    ScrollHalfPageUp(usize),
+    ScrollPageDown(usize),
+    ScrollPageUp(usize),
     InsertAtCursor,
     InsertAtLineStart,
     InsertAfterCursor,
@@ -140,12 +162,74 @@ pub enum NormalModeTransition {
     Undo,
     Redo,
     YankLines(usize),
-    DeleteInsideWord(usize),
+    DeleteTextObject(TextObject, usize),
+    /// Like `DeleteTextObject`, but whole lines leave an empty line to insert into.
+    ChangeTextObject(TextObject, usize),
+    YankTextObject(TextObject, usize),
+    DeleteMotion(Motion, usize),
+    ChangeMotion(Motion, usize),
+    YankMotion(Motion, usize),
+    /// `>>`, indenting `n` lines by the configured width.
+    IndentLines(usize),
+    OutdentLines(usize),
+    /// `J`, joining `n` lines into one, at least two.
+    JoinLines(usize),
+    /// `~`, moving the cursor past the changed characters.
+    CaseChars(CaseKind, usize),
+    CaseLines(CaseKind, usize),
+    CaseWordEnd(CaseKind, usize),
+    CaseWordBack(CaseKind, usize),
+    CaseLineStart(CaseKind),
+    CaseLineEnd(CaseKind, usize),
+    CaseToChar(CaseKind, CharFind, usize),
+    CaseTextObject(CaseKind, TextObject, usize),
+    CaseMotion(CaseKind, Motion, usize),
+    /// `r`, which does nothing when the line has fewer than `n` characters left.
+    ReplaceChars(char, usize),
+    /// `R`
+    ReplaceMode,
+    SearchMode(SearchDirection),
+    ConfirmSearch(SearchDirection, String),
+    CancelSearch,
+    SearchNext(usize),
+    SearchPrev(usize),
+    ClearSearch,
+    CommandMode,
+    /// Closes the command line, on `Esc` or once its command is run.
+    CloseCommand,
+    SaveNote,
+    SaveAllNotes,
+    MoveCursorToAddress(Address),
+    Substitute(Substitution),
+    RegisterMode,
+    SelectRegister(char),
+    ShowRegisters,
+    FindMode,
+    /// Set for `;` and `,`, which skip a `t` or `T` target right next to the cursor.
+    MoveCursorToChar(CharFind, usize, bool),
+    DeleteToChar(CharFind, usize),
+    YankToChar(CharFind, usize),
+    RecordMacroMode,
+    PlayMacroMode,
+    StartRecording(char),
+    StopRecording(char),
+    MarkMode,
+    JumpToMarkMode,
+    SetMark(char),
+    /// `'{mark}` when set, going to the first non-blank of the line of the mark.
+    JumpToMark(char, bool),
+    /// `Ctrl+o`, which may open the note the older position is in.
+    JumpBack(usize),
+    JumpForward(usize),
 }
 
 #[derive(Display)]
 pub enum VisualModeTransition {
     IdleMode,
+    LinewiseMode,
+    BlockwiseMode,
+    /// Switched from another kind of visual mode, keeping where the selection started.
+    ChangeKind(VisualKind),
     NumberingMode,
     GatewayMode,
     MoveCursorDown(usize),
@@ -161,9 +245,83 @@ pub enum VisualModeTransition {
     MoveCursorTop,
     MoveCursorBottom,
     MoveCursorToLine(usize),
+    MoveCursor(Motion, usize),
+    ScrollHalfPageDown(usize),
+    ScrollHalfPageUp(usize),
+    ScrollPageDown(usize),
+    ScrollPageUp(usize),
     YankSelection,
     DeleteSelection,
     DeleteSelectionAndInsertMode,
+    RegisterMode,
+    SelectRegister(char),
+    FindMode,
+    /// Set for `;` and `,`, as in [`NormalModeTransition::MoveCursorToChar`].
+    MoveCursorToChar(CharFind, usize, bool),
+    TextObjectMode,
+    /// Selected as the kind the core has switched to, which is linewise for paragraphs.
+    SelectTextObject(TextObject, VisualKind, usize),
+    /// `>` on the selected lines, `n` times.
+    IndentSelection(usize),
+    OutdentSelection(usize),
+    /// `I` in blockwise visual mode, inserting the typed text on every line of the block.
+    InsertBlock,
+    /// `A` in blockwise visual mode.
+    AppendBlock,
+}
+
+#[derive(Display)]
+pub enum InsertModeTransition {
+    /// A key typed in insert mode, for the frontend to apply to the text.
+    #[strum(to_string = "Key::{0}")]
+    Key(KeyEvent),
+    /// A key typed in replace mode, which writes over the character under the cursor.
+    #[strum(to_string = "Overwrite::{0}")]
+    Overwrite(KeyEvent),
+}
+
+impl NormalModeTransition {
+    /// Whether the transition edits the note, which makes its command repeatable with `.`.
+    pub fn is_change(&self) -> bool {
+        use NormalModeTransition::*;
+
+        matches!(
+            self,
+            InsertAtCursor
+                | InsertAtLineStart
+                | InsertAfterCursor
+                | InsertAtLineEnd
+                | InsertNewLineBelow
+                | InsertNewLineAbove
+                | DeleteChars(_)
+                | DeleteLines(_)
+                | DeleteLinesAndInsert(_)
+                | DeleteWordEnd(_)
+                | DeleteWordBack(_)
+                | DeleteLineStart
+                | DeleteLineEnd(_)
+                | DeleteTextObject(..)
+                | ChangeTextObject(..)
+                | DeleteToChar(..)
+                | IndentLines(_)
+                | OutdentLines(_)
+                | JoinLines(_)
+                | CaseChars(..)
+                | CaseLines(..)
+                | CaseWordEnd(..)
+                | CaseWordBack(..)
+                | CaseLineStart(_)
+                | CaseLineEnd(..)
+                | CaseToChar(..)
+                | CaseTextObject(..)
+                | CaseMotion(..)
+                | DeleteMotion(..)
+                | ChangeMotion(..)
+                | ReplaceChars(..)
+                | ReplaceMode
+                | Paste
+        )
+    }
 }
 
 impl From<EntryTransition> for Transition {
@@ -4,16 +4,17 @@ use {
             self, LAST_CSV_PATH, LAST_FILE_PATH, LAST_GIT_BRANCH, LAST_GIT_PATH, LAST_GIT_REMOTE,
             LAST_JSON_PATH, LAST_MONGO_CONN_STR, LAST_MONGO_DB_NAME,
         },
-        context::ContextPrompt,
+        context::{ContextPrompt, LogViewer},
         logger::*,
-        App,
+        settings::Autosave,
+        swap, App,
     },
-    glues_core::{EntryEvent, Event, KeyEvent, NotebookEvent, NumKey},
+    glues_core::{EntryEvent, Event, KeyEvent, NotebookEvent},
     ratatui::{
         crossterm::event::{Event as Input, KeyCode, KeyModifiers},
-        style::Stylize,
         text::Line,
     },
+    std::time::SystemTime,
 };
 
 #[derive(Clone)]
@@ -43,9 +44,16 @@ pub enum TuiAction {
     },
     Help,
     ShowEditorKeymap,
+    ShowLogs,
     SaveAndPassThrough,
+    Save,
+    SaveAll,
+    RecoverSwaps,
+    DiscardSwaps,
+    CloseNotebook,
     Quit,
 
+    Open(EntryEvent),
     OpenCsv,
     OpenJson,
     OpenFile,
@@ -63,7 +71,13 @@ pub enum TuiAction {
 #[derive(Clone)]
 pub enum OpenMongoStep {
     ConnStr,
-    Database { conn_str: String },
+    Database {
+        conn_str: String,
+    },
+    /// A recent notebook, whose credentials were not remembered.
+    Reconnect {
+        db_name: String,
+    },
 }
 
 #[derive(Clone)]
@@ -81,7 +95,7 @@ impl From<TuiAction> for Action {
 
 impl From<EntryEvent> for Action {
     fn from(event: EntryEvent) -> Self {
-        Self::Dispatch(event.into())
+        TuiAction::Open(event).into()
     }
 }
 
@@ -89,14 +103,51 @@ impl App {
     pub(super) async fn handle_action(&mut self, action: Action, input: Input) -> bool {
         match action {
             Action::Tui(TuiAction::Quit) => {
+                self.save_session();
                 return true;
             }
+            Action::Tui(TuiAction::CloseNotebook) => {
+                // saved by the core, which keeps the notebook open if any of them fails
+                let unsaved: Vec<_> = self
+                    .context
+                    .notebook
+                    .tabs
+                    .iter()
+                    .filter(|tab| tab.dirty)
+                    .map(|tab| (tab.note.id.clone(), tab.editor.lines().join("\n")))
+                    .collect();
+                let note_ids: Vec<_> = unsaved.iter().map(|(note_id, _)| note_id.clone()).collect();
+
+                let event = NotebookEvent::CloseNotebook { unsaved }.into();
+                let transition = self.glues.dispatch(event).await.log_unwrap();
+                for note_id in note_ids {
+                    swap::remove(&note_id);
+                }
+                self.handle_transition(transition).await;
+            }
             Action::Tui(TuiAction::Help) => {
                 self.context.help = true;
             }
             Action::Tui(TuiAction::ShowEditorKeymap) => {
                 self.context.editor_keymap = true;
             }
+            Action::Tui(TuiAction::ShowLogs) => {
+                self.context.log_viewer = Some(LogViewer::load());
+            }
+            Action::Tui(TuiAction::Save) => {
+                self.save_current().await;
+                self.context.last_log = Some(("Note saved".to_owned(), SystemTime::now()));
+            }
+            Action::Tui(TuiAction::SaveAll) => {
+                self.save().await;
+                self.context.last_log = Some(("All notes saved".to_owned(), SystemTime::now()));
+            }
+            Action::Tui(TuiAction::RecoverSwaps) => {
+                self.recover_swaps().await;
+            }
+            Action::Tui(TuiAction::DiscardSwaps) => {
+                self.discard_swaps();
+            }
             Action::Tui(TuiAction::Alert(message)) => {
                 self.context.alert = Some(message);
             }
@@ -114,13 +165,16 @@ impl App {
             }) => {
                 self.context.prompt = Some(ContextPrompt::new(message, *action, default));
             }
+            Action::Tui(TuiAction::Open(event)) => {
+                self.open(event).await;
+            }
             Action::Tui(TuiAction::OpenGit(OpenGitStep::Path)) => {
                 let path = self
                     .context
                     .take_prompt_input()
                     .log_expect("prompt must not be none");
                 let message = vec![
-                    Line::from(format!("path: {path}").dark_gray()),
+                    Line::styled(format!("path: {path}"), self.context.settings.theme.hint),
                     Line::raw(""),
                     Line::raw("Enter the git remote:"),
                 ];
@@ -136,8 +190,11 @@ impl App {
                     .take_prompt_input()
                     .log_expect("prompt must not be none");
                 let message = vec![
-                    Line::from(format!("path: {path}").dark_gray()),
-                    Line::from(format!("remote: {remote}").dark_gray()),
+                    Line::styled(format!("path: {path}"), self.context.settings.theme.hint),
+                    Line::styled(
+                        format!("remote: {remote}"),
+                        self.context.settings.theme.hint,
+                    ),
                     Line::raw(""),
                     Line::raw("Enter the git branch:"),
                 ];
@@ -152,19 +209,12 @@ impl App {
                     .context
                     .take_prompt_input()
                     .log_expect("branch must not be none");
-                let transition = self
-                    .glues
-                    .dispatch(
-                        EntryEvent::OpenGit {
-                            path,
-                            remote,
-                            branch,
-                        }
-                        .into(),
-                    )
-                    .await
-                    .log_unwrap();
-                self.handle_transition(transition).await;
+                self.open(EntryEvent::OpenGit {
+                    path,
+                    remote,
+                    branch,
+                })
+                .await;
             }
             Action::Tui(TuiAction::OpenMongo(OpenMongoStep::ConnStr)) => {
                 let conn_str = self
@@ -172,7 +222,10 @@ impl App {
                     .take_prompt_input()
                     .log_expect("conn str must not be none");
                 let message = vec![
-                    Line::from(format!("conn_str: {conn_str}").dark_gray()),
+                    Line::styled(
+                        format!("conn_str: {conn_str}"),
+                        self.context.settings.theme.hint,
+                    ),
                     Line::raw(""),
                     Line::raw("Enter the database name:"),
                 ];
@@ -191,12 +244,15 @@ impl App {
 
                 config::update(LAST_MONGO_DB_NAME, &db_name).await;
 
-                let transition = self
-                    .glues
-                    .dispatch(EntryEvent::OpenMongo { conn_str, db_name }.into())
-                    .await
-                    .log_unwrap();
-                self.handle_transition(transition).await;
+                self.open(EntryEvent::OpenMongo { conn_str, db_name }).await;
+            }
+            Action::Tui(TuiAction::OpenMongo(OpenMongoStep::Reconnect { db_name })) => {
+                let conn_str = self
+                    .context
+                    .take_prompt_input()
+                    .log_expect("conn str must not be none");
+
+                self.open(EntryEvent::OpenMongo { conn_str, db_name }).await;
             }
             Action::Tui(TuiAction::OpenCsv) => {
                 let path = self
@@ -210,12 +266,7 @@ impl App {
 
                 config::update(LAST_CSV_PATH, &path).await;
 
-                let transition = self
-                    .glues
-                    .dispatch(EntryEvent::OpenCsv(path).into())
-                    .await
-                    .log_unwrap();
-                self.handle_transition(transition).await;
+                self.open(EntryEvent::OpenCsv(path)).await;
             }
             Action::Tui(TuiAction::OpenJson) => {
                 let path = self
@@ -229,12 +280,7 @@ impl App {
 
                 config::update(LAST_JSON_PATH, &path).await;
 
-                let transition = self
-                    .glues
-                    .dispatch(EntryEvent::OpenJson(path).into())
-                    .await
-                    .log_unwrap();
-                self.handle_transition(transition).await;
+                self.open(EntryEvent::OpenJson(path)).await;
             }
             Action::Tui(TuiAction::OpenFile) => {
                 let path = self
@@ -248,12 +294,7 @@ impl App {
 
                 config::update(LAST_FILE_PATH, &path).await;
 
-                let transition = self
-                    .glues
-                    .dispatch(EntryEvent::OpenFile(path).into())
-                    .await
-                    .log_unwrap();
-                self.handle_transition(transition).await;
+                self.open(EntryEvent::OpenFile(path)).await;
             }
             Action::Tui(TuiAction::RenameNote) => {
                 let new_name = self
@@ -345,7 +386,9 @@ impl App {
             }
 
             Action::Tui(TuiAction::SaveAndPassThrough) => {
-                self.save().await;
+                if self.context.settings.editor.autosave == Autosave::Idle {
+                    self.save().await;
+                }
 
                 let event = match to_event(input) {
                     Some(event) => event.into(),
@@ -375,60 +418,29 @@ impl App {
     }
 }
 
-fn to_event(input: Input) -> Option<KeyEvent> {
+pub(crate) fn to_event(input: Input) -> Option<KeyEvent> {
     let key = match input {
         Input::Key(key) => key,
         _ => return None,
     };
-    let code = key.code;
-    let ctrl = key.modifiers == KeyModifiers::CONTROL;
-
-    let event = match code {
-        KeyCode::Char('h') if ctrl => KeyEvent::CtrlH,
-        KeyCode::Char('r') if ctrl => KeyEvent::CtrlR,
-        KeyCode::Char('a') => KeyEvent::A,
-        KeyCode::Char('b') => KeyEvent::B,
-        KeyCode::Char('c') => KeyEvent::C,
-        KeyCode::Char('d') => KeyEvent::D,
-        KeyCode::Char('e') => KeyEvent::E,
-        KeyCode::Char('g') => KeyEvent::G,
-        KeyCode::Char('h') => KeyEvent::H,
-        KeyCode::Char('i') => KeyEvent::I,
-        KeyCode::Char('j') => KeyEvent::J,
-        KeyCode::Char('k') => KeyEvent::K,
-        KeyCode::Char('l') => KeyEvent::L,
-        KeyCode::Char('m') => KeyEvent::M,
-        KeyCode::Char('n') => KeyEvent::N,
-        KeyCode::Char('o') => KeyEvent::O,
-        KeyCode::Char('p') => KeyEvent::P,
-        KeyCode::Char('s') => KeyEvent::S,
-        KeyCode::Char('t') => KeyEvent::T,
-        KeyCode::Char('u') => KeyEvent::U,
-        KeyCode::Char('v') => KeyEvent::V,
-        KeyCode::Char('w') => KeyEvent::W,
-        KeyCode::Char('x') => KeyEvent::X,
-        KeyCode::Char('y') => KeyEvent::Y,
-        KeyCode::Char('A') => KeyEvent::CapA,
-        KeyCode::Char('G') => KeyEvent::CapG,
-        KeyCode::Char('I') => KeyEvent::CapI,
-        KeyCode::Char('O') => KeyEvent::CapO,
-        KeyCode::Char('S') => KeyEvent::CapS,
-        KeyCode::Char('1') => NumKey::One.into(),
-        KeyCode::Char('2') => NumKey::Two.into(),
-        KeyCode::Char('3') => NumKey::Three.into(),
-        KeyCode::Char('4') => NumKey::Four.into(),
-        KeyCode::Char('5') => NumKey::Five.into(),
-        KeyCode::Char('6') => NumKey::Six.into(),
-        KeyCode::Char('7') => NumKey::Seven.into(),
-        KeyCode::Char('8') => NumKey::Eight.into(),
-        KeyCode::Char('9') => NumKey::Nine.into(),
-        KeyCode::Char('0') => NumKey::Zero.into(),
-        KeyCode::Char('$') => KeyEvent::DollarSign,
-        KeyCode::Char('^') => KeyEvent::Caret,
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    let alt = key.modifiers.contains(KeyModifiers::ALT);
+
+    let event = match key.code {
+        KeyCode::Char(c) if ctrl => KeyEvent::Ctrl(c),
+        KeyCode::Char(c) if alt => KeyEvent::Alt(c),
+        KeyCode::Char(c) => KeyEvent::Char(c),
         KeyCode::Left => KeyEvent::Left,
         KeyCode::Right => KeyEvent::Right,
         KeyCode::Up => KeyEvent::Up,
         KeyCode::Down => KeyEvent::Down,
+        KeyCode::Home => KeyEvent::Home,
+        KeyCode::End => KeyEvent::End,
+        KeyCode::PageUp => KeyEvent::PageUp,
+        KeyCode::PageDown => KeyEvent::PageDown,
+        KeyCode::Tab => KeyEvent::Tab,
+        KeyCode::Backspace => KeyEvent::Backspace,
+        KeyCode::Delete => KeyEvent::Delete,
         KeyCode::Enter => KeyEvent::Enter,
         KeyCode::Esc => KeyEvent::Esc,
         _ => return None,
@@ -436,3 +448,20 @@ fn to_event(input: Input) -> Option<KeyEvent> {
 
     Some(event)
 }
+
+/// Whether `to_event` keeps all of the key, so that it can be typed back as it was.
+/// Shift is only kept in the character it types.
+pub(crate) fn is_lossless(input: &Input) -> bool {
+    let key = match input {
+        Input::Key(key) => key,
+        _ => return false,
+    };
+    let modifiers = match key.code {
+        KeyCode::Char(_) => key.modifiers.difference(KeyModifiers::SHIFT),
+        _ => key.modifiers,
+    };
+
+    [KeyModifiers::NONE, KeyModifiers::CONTROL, KeyModifiers::ALT].contains(&modifiers)
+        && (modifiers.is_empty() || matches!(key.code, KeyCode::Char(_)))
+        && to_event(input.clone()).is_some()
+}
@@ -0,0 +1,124 @@
+use {
+    crate::settings::Clipboard,
+    base64::{engine::general_purpose::STANDARD, Engine},
+    std::{
+        env,
+        io::{self, Write},
+        process::{Command, Stdio},
+    },
+};
+
+struct Provider {
+    copy: &'static [&'static str],
+    paste: &'static [&'static str],
+    /// Environment variable of the display server the commands talk to.
+    display: Option<&'static str>,
+}
+
+/// Clipboard commands, tried in order.
+const PROVIDERS: [Provider; 4] = [
+    Provider {
+        copy: &["wl-copy"],
+        paste: &["wl-paste", "--no-newline"],
+        display: Some("WAYLAND_DISPLAY"),
+    },
+    Provider {
+        copy: &["xclip", "-selection", "clipboard"],
+        paste: &["xclip", "-selection", "clipboard", "-o"],
+        display: Some("DISPLAY"),
+    },
+    Provider {
+        copy: &["xsel", "--clipboard", "--input"],
+        paste: &["xsel", "--clipboard", "--output"],
+        display: Some("DISPLAY"),
+    },
+    Provider {
+        copy: &["pbcopy"],
+        paste: &["pbpaste"],
+        display: None,
+    },
+];
+
+fn installed(program: &str) -> bool {
+    env::var_os("PATH")
+        .is_some_and(|paths| env::split_paths(&paths).any(|dir| dir.join(program).is_file()))
+}
+
+fn provider() -> Option<&'static Provider> {
+    PROVIDERS.iter().find(|provider| {
+        provider
+            .display
+            .map_or(true, |name| env::var_os(name).is_some())
+            && installed(provider.copy[0])
+    })
+}
+
+/// Writes `text` to the system clipboard.
+pub fn copy(clipboard: Clipboard, text: &str) {
+    let result = match (clipboard, provider()) {
+        (Clipboard::None, _) => return,
+        (Clipboard::Auto, Some(provider)) => run_copy(provider, text),
+        (Clipboard::Auto, None) | (Clipboard::Osc52, _) => osc52(text),
+    };
+
+    if let Err(error) = result {
+        warn!("failed to copy to the clipboard: {error}");
+    }
+}
+
+/// Reads the system clipboard, `None` when it cannot be read and the register is used instead.
+pub fn paste(clipboard: Clipboard) -> Option<String> {
+    if clipboard != Clipboard::Auto {
+        return None;
+    }
+
+    let provider = provider()?;
+    let output = Command::new(provider.paste[0])
+        .args(&provider.paste[1..])
+        .stderr(Stdio::null())
+        .output();
+
+    match output {
+        Ok(output) if output.status.success() => {
+            Some(String::from_utf8_lossy(&output.stdout).into_owned())
+        }
+        Ok(output) => {
+            warn!("{} exited with {}", provider.paste[0], output.status);
+            None
+        }
+        Err(error) => {
+            warn!("failed to run {}: {error}", provider.paste[0]);
+            None
+        }
+    }
+}
+
+fn run_copy(provider: &Provider, text: &str) -> io::Result<()> {
+    let mut child = Command::new(provider.copy[0])
+        .args(&provider.copy[1..])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(text.as_bytes())?;
+    }
+    child.wait()?;
+
+    Ok(())
+}
+
+/// Asks the terminal to set the clipboard, which also works over ssh.
+fn osc52(text: &str) -> io::Result<()> {
+    let sequence = format!("\x1b]52;c;{}\x07", STANDARD.encode(text));
+    // tmux only passes the sequence through to the outer terminal when wrapped
+    let sequence = match env::var_os("TMUX") {
+        Some(_) => format!("\x1bPtmux;{}\x1b\\", sequence.replace('\x1b', "\x1b\x1b")),
+        None => sequence,
+    };
+
+    let mut stdout = io::stdout();
+    stdout.write_all(sequence.as_bytes())?;
+    stdout.flush()
+}
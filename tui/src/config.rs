@@ -1,11 +1,14 @@
 use {
     crate::logger::*,
     gluesql::{
-        core::ast_builder::{col, table, text, Execute},
-        prelude::{CsvStorage, Glue},
+        core::{
+            ast_builder::{col, function::now, num, table, text, Execute},
+            chrono::NaiveDateTime,
+        },
+        prelude::{CsvStorage, Glue, Value},
     },
     home::home_dir,
-    std::ops::Deref,
+    std::{ops::Deref, path::PathBuf},
 };
 
 pub const LAST_CSV_PATH: &str = "last_csv_path";
@@ -19,11 +22,23 @@ pub const LAST_MONGO_DB_NAME: &str = "last_mongo_db_name";
 
 const PATH: &str = ".glues/";
 
-pub fn get_glue() -> Glue<CsvStorage> {
-    let path = home_dir()
+pub const MAX_RECENT: usize = 5;
+
+#[derive(Clone)]
+pub struct RecentNotebook {
+    pub kind: String,
+    pub locator: String,
+    pub opened_at: NaiveDateTime,
+}
+
+pub fn dir() -> PathBuf {
+    home_dir()
         .unwrap_or(std::env::current_dir().expect("failed to get current directory"))
-        .join(PATH);
-    let storage = CsvStorage::new(path).unwrap();
+        .join(PATH)
+}
+
+pub fn get_glue() -> Glue<CsvStorage> {
+    let storage = CsvStorage::new(dir()).unwrap();
 
     Glue::new(storage)
 }
@@ -39,6 +54,15 @@ pub async fn init() {
         .await
         .unwrap();
 
+    table("recent")
+        .create_table_if_not_exists()
+        .add_column("locator TEXT PRIMARY KEY")
+        .add_column("kind TEXT NOT NULL")
+        .add_column("opened_at TIMESTAMP NOT NULL DEFAULT NOW()")
+        .execute(&mut glue)
+        .await
+        .unwrap();
+
     for (key, value) in [
         (LAST_CSV_PATH, ""),
         (LAST_JSON_PATH, ""),
@@ -90,3 +114,79 @@ pub async fn get(key: &str) -> Option<String> {
 
     Some(value)
 }
+
+pub async fn touch_recent(kind: &str, locator: &str) {
+    let mut glue = get_glue();
+
+    table("recent")
+        .delete()
+        .filter(col("locator").eq(text(locator.to_owned())))
+        .execute(&mut glue)
+        .await
+        .log_unwrap();
+
+    table("recent")
+        .insert()
+        .columns(vec!["locator", "kind", "opened_at"])
+        .values(vec![vec![text(locator), text(kind), now()]])
+        .execute(&mut glue)
+        .await
+        .log_unwrap();
+
+    let stale = table("recent")
+        .select()
+        .project("locator")
+        .order_by("opened_at DESC")
+        .offset(num(MAX_RECENT as i64))
+        .execute(&mut glue)
+        .await
+        .log_unwrap()
+        .select()
+        .log_expect("payload is not from select query")
+        .filter_map(|row| row.get("locator").map(Deref::deref).map(Into::into))
+        .collect::<Vec<String>>();
+
+    for locator in stale {
+        table("recent")
+            .delete()
+            .filter(col("locator").eq(text(locator)))
+            .execute(&mut glue)
+            .await
+            .log_unwrap();
+    }
+}
+
+pub async fn recent() -> Vec<RecentNotebook> {
+    let mut glue = get_glue();
+
+    table("recent")
+        .select()
+        .project(vec!["locator", "kind", "opened_at"])
+        .order_by("opened_at DESC")
+        .limit(num(MAX_RECENT as i64))
+        .execute(&mut glue)
+        .await
+        .log_unwrap()
+        .select()
+        .log_expect("payload is not from select query")
+        .filter_map(|row| {
+            let opened_at = match row.get("opened_at").map(Deref::deref) {
+                Some(Value::Timestamp(opened_at)) => *opened_at,
+                opened_at => {
+                    warn!("Skipping recent notebook, opened_at is not a timestamp: {opened_at:?}");
+                    return None;
+                }
+            };
+
+            Some(RecentNotebook {
+                kind: row.get("kind").map(Deref::deref).log_expect("kind").into(),
+                locator: row
+                    .get("locator")
+                    .map(Deref::deref)
+                    .log_expect("locator")
+                    .into(),
+                opened_at,
+            })
+        })
+        .collect()
+}
@@ -1,19 +1,20 @@
 pub mod entry;
+pub mod log_viewer;
 pub mod notebook;
 
 use {
-    crate::{log, logger::*, Action},
+    crate::{action::TuiAction, keymap::Mode, logger::*, settings::Settings, swap::Swap, Action},
     glues_core::transition::VimKeymapKind,
     ratatui::{
-        crossterm::event::{Event as Input, KeyCode, KeyEvent},
-        style::{Style, Stylize},
+        crossterm::event::{Event as Input, KeyCode, KeyEvent, KeyModifiers},
+        style::Style,
         text::Line,
         widgets::{Block, Borders},
     },
     std::time::SystemTime,
     tui_textarea::TextArea,
 };
-pub use {entry::EntryContext, notebook::NotebookContext};
+pub use {entry::EntryContext, log_viewer::LogViewer, notebook::NotebookContext};
 
 pub enum ContextState {
     Entry,
@@ -29,7 +30,6 @@ pub struct ContextPrompt {
 impl ContextPrompt {
     pub fn new(message: Vec<Line<'static>>, action: Action, default: Option<String>) -> Self {
         let mut widget = TextArea::new(vec![default.unwrap_or_default()]);
-        widget.set_cursor_style(Style::default().white().on_blue());
         widget.set_block(
             Block::default()
                 .border_style(Style::default())
@@ -48,15 +48,20 @@ pub struct Context {
     pub notebook: NotebookContext,
 
     pub state: ContextState,
+    pub settings: Settings,
 
     pub confirm: Option<(String, Action)>,
     pub alert: Option<String>,
     pub prompt: Option<ContextPrompt>,
     pub last_log: Option<(String, SystemTime)>,
+    pub log_viewer: Option<LogViewer>,
+    /// Unsaved changes of an earlier session, waiting to be recovered or discarded.
+    pub swaps: Option<Vec<Swap>>,
 
     pub help: bool,
     pub editor_keymap: bool,
     pub vim_keymap: Option<VimKeymapKind>,
+    pub register_viewer: bool,
 }
 
 impl Default for Context {
@@ -66,14 +71,18 @@ impl Default for Context {
             notebook: NotebookContext::default(),
 
             state: ContextState::Entry,
+            settings: Settings::default(),
             confirm: None,
             alert: None,
             prompt: None,
             last_log: None,
+            log_viewer: None,
+            swaps: None,
 
             help: false,
             editor_keymap: false,
             vim_keymap: None,
+            register_viewer: false,
         }
     }
 }
@@ -88,10 +97,85 @@ impl Context {
             .map(ToOwned::to_owned)
     }
 
+    /// Bracketed paste arrives as a single event, so it is inserted at once instead of key by key.
+    pub fn paste(&mut self, text: &str) {
+        if let Some(prompt) = self.prompt.as_mut() {
+            prompt
+                .widget
+                .insert_str(text.lines().next().unwrap_or_default());
+        } else if matches!(self.state, ContextState::Notebook) && self.alert.is_none() {
+            self.notebook.paste(text);
+        }
+    }
+
+    pub fn keymap_mode(&self) -> Mode {
+        match self.state {
+            ContextState::Entry => Mode::Entry,
+            ContextState::Notebook => self.notebook.state.keymap_mode(),
+        }
+    }
+
+    /// Applies the user keymap, except in dialogs which read keys as they are.
+    pub fn translate(&self, input: Input) -> Option<Input> {
+        if self.log_viewer.is_some()
+            || self.vim_keymap.is_some()
+            || self.register_viewer
+            || self.editor_keymap
+            || self.help
+            || self.alert.is_some()
+            || self.confirm.is_some()
+            || self.prompt.is_some()
+            || self.swaps.is_some()
+            || self.notebook.command_line.is_some()
+        {
+            return Some(input);
+        }
+
+        self.settings.keymap.translate(self.keymap_mode(), input)
+    }
+
     pub async fn consume(&mut self, input: &Input) -> Action {
-        if self.vim_keymap.is_some() {
+        let show_logs = matches!(
+            input,
+            Input::Key(KeyEvent {
+                code: KeyCode::Char('l'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            })
+        );
+
+        if let Some(log_viewer) = self.log_viewer.as_mut() {
+            if let Input::Key(key) = input {
+                if !log_viewer.consume(key) {
+                    self.log_viewer = None;
+                }
+            }
+
+            return Action::None;
+        } else if show_logs && self.confirm.is_none() && self.prompt.is_none() {
+            self.alert = None;
+            return TuiAction::ShowLogs.into();
+        } else if self.swaps.is_some() {
+            let code = match input {
+                Input::Key(key) => key.code,
+                _ => return Action::None,
+            };
+
+            return match code {
+                KeyCode::Char('r') => TuiAction::RecoverSwaps.into(),
+                KeyCode::Char('d') => TuiAction::DiscardSwaps.into(),
+                KeyCode::Esc => {
+                    self.swaps = None;
+                    Action::None
+                }
+                _ => Action::None,
+            };
+        } else if self.vim_keymap.is_some() {
             self.vim_keymap = None;
             return Action::None;
+        } else if self.register_viewer {
+            self.register_viewer = false;
+            return Action::None;
         } else if self.editor_keymap {
             self.editor_keymap = false;
             return Action::None;
@@ -111,7 +195,7 @@ impl Context {
             match code {
                 KeyCode::Char('y') => {
                     let (_, action) = self.confirm.take().log_expect("confirm must be some");
-                    log!("Context::consume - remove note!!!");
+                    debug!("Context::consume - confirmed");
                     return action;
                 }
                 KeyCode::Char('n') => {
@@ -148,7 +232,7 @@ impl Context {
 
         match self.state {
             ContextState::Entry => match input {
-                Input::Key(key) => self.entry.consume(key.code).await,
+                Input::Key(key) => self.entry.consume(key.code, &self.settings.theme).await,
                 _ => Action::None,
             },
             ContextState::Notebook => self.notebook.consume(input),
@@ -2,12 +2,15 @@ use {
     crate::{
         action::{Action, OpenGitStep, OpenMongoStep, TuiAction},
         config::{
-            self, LAST_CSV_PATH, LAST_FILE_PATH, LAST_GIT_PATH, LAST_JSON_PATH, LAST_MONGO_CONN_STR,
+            self, RecentNotebook, LAST_CSV_PATH, LAST_FILE_PATH, LAST_GIT_PATH, LAST_JSON_PATH,
+            LAST_MONGO_CONN_STR,
         },
+        locator,
         logger::*,
+        theme::Theme,
     },
     glues_core::EntryEvent,
-    ratatui::{crossterm::event::KeyCode, style::Stylize, text::Line, widgets::ListState},
+    ratatui::{crossterm::event::KeyCode, style::Style, text::Line, widgets::ListState},
 };
 
 pub const INSTANT: &str = "[1] Instant";
@@ -21,25 +24,30 @@ pub const QUIT: &str = "[q] Quit";
 
 pub const MENU_ITEMS: [&str; 8] = [INSTANT, FILE, GIT, MONGO, CSV, JSON, HELP, QUIT];
 
+pub const RECENT_KEYS: [char; config::MAX_RECENT] = ['a', 'b', 'c', 'd', 'e'];
+
 pub struct EntryContext {
     pub list_state: ListState,
+    pub recent: Vec<RecentNotebook>,
 }
 
 impl Default for EntryContext {
     fn default() -> Self {
         Self {
             list_state: ListState::default().with_selected(Some(0)),
+            recent: Vec::new(),
         }
     }
 }
 
 impl EntryContext {
-    pub async fn consume(&mut self, code: KeyCode) -> Action {
+    pub async fn consume(&mut self, code: KeyCode, theme: &Theme) -> Action {
+        let hint = theme.hint;
         let open = |key, action: TuiAction| async move {
             TuiAction::Prompt {
                 message: vec![
                     Line::raw("Enter the path:"),
-                    Line::from("If path not exists, it will be created.".dark_gray()),
+                    Line::styled("If path not exists, it will be created.", hint),
                 ],
                 action: Box::new(action.into()),
                 default: config::get(key).await,
@@ -51,8 +59,8 @@ impl EntryContext {
             TuiAction::Prompt {
                 message: vec![
                     Line::raw("Enter the git repository path:"),
-                    Line::from("The path must contain an existing .git repository;".dark_gray()),
-                    Line::from("otherwise, an error will occur.".dark_gray()),
+                    Line::styled("The path must contain an existing .git repository;", hint),
+                    Line::styled("otherwise, an error will occur.", hint),
                 ],
                 action: Box::new(TuiAction::OpenGit(OpenGitStep::Path).into()),
                 default: config::get(LAST_GIT_PATH).await,
@@ -64,7 +72,7 @@ impl EntryContext {
             TuiAction::Prompt {
                 message: vec![
                     Line::raw("Enter the MongoDB connection string:"),
-                    Line::from("e.g. mongodb://localhost:27017".dark_gray()),
+                    Line::styled("e.g. mongodb://localhost:27017", hint),
                 ],
                 action: Box::new(TuiAction::OpenMongo(OpenMongoStep::ConnStr).into()),
                 default: config::get(LAST_MONGO_CONN_STR).await,
@@ -73,6 +81,14 @@ impl EntryContext {
         };
 
         match code {
+            KeyCode::Char(c) if RECENT_KEYS.contains(&c) => {
+                let i = RECENT_KEYS
+                    .iter()
+                    .position(|key| *key == c)
+                    .log_expect("recent key must exist");
+
+                self.open_recent(i, hint)
+            }
             KeyCode::Char('q') => TuiAction::Quit.into(),
             KeyCode::Char('j') | KeyCode::Down => {
                 self.list_state.select_next();
@@ -110,4 +126,26 @@ impl EntryContext {
             _ => Action::None,
         }
     }
+
+    fn open_recent(&self, i: usize, hint: Style) -> Action {
+        let RecentNotebook { locator, .. } = match self.recent.get(i) {
+            Some(recent) => recent,
+            None => return Action::None,
+        };
+
+        match locator::parse(locator) {
+            Ok(EntryEvent::OpenMongo { conn_str, db_name }) => TuiAction::Prompt {
+                message: vec![
+                    Line::raw("Enter the MongoDB connection string:"),
+                    Line::styled(format!("database: {db_name}"), hint),
+                    Line::styled("Credentials are not remembered.", hint),
+                ],
+                action: Box::new(TuiAction::OpenMongo(OpenMongoStep::Reconnect { db_name }).into()),
+                default: Some(locator::redact(&conn_str)),
+            }
+            .into(),
+            Ok(event) => event.into(),
+            Err(message) => TuiAction::Alert(message).into(),
+        }
+    }
 }
@@ -0,0 +1,60 @@
+use {
+    crate::logger::{self, LogEntry, MAX_ROTATIONS},
+    log::Level,
+    ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+};
+
+const PAGE: u16 = 10;
+
+pub struct LogViewer {
+    /// Entries of the current session first, then of the previous ones.
+    pub sessions: Vec<Vec<LogEntry>>,
+    pub scroll: u16,
+    pub errors_only: bool,
+}
+
+impl LogViewer {
+    pub fn load() -> Self {
+        let sessions = (0..=MAX_ROTATIONS).map(logger::read).collect();
+
+        Self {
+            sessions,
+            scroll: 0,
+            errors_only: false,
+        }
+    }
+
+    pub fn is_visible(&self, entry: &LogEntry) -> bool {
+        !self.errors_only || entry.level <= Level::Warn
+    }
+
+    /// Returns `false` once the viewer should be closed.
+    pub fn consume(&mut self, key: &KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+
+        match key.code {
+            KeyCode::Char('d') if ctrl => self.scroll = self.scroll.saturating_add(PAGE),
+            KeyCode::Char('u') if ctrl => self.scroll = self.scroll.saturating_sub(PAGE),
+            KeyCode::PageDown => self.scroll = self.scroll.saturating_add(PAGE),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(PAGE),
+            KeyCode::Char('j') | KeyCode::Down => self.scroll = self.scroll.saturating_add(1),
+            KeyCode::Char('k') | KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Char('g') => self.scroll = 0,
+            KeyCode::Char('G') => self.scroll = u16::MAX,
+            KeyCode::Char('e') => {
+                self.errors_only = !self.errors_only;
+                self.scroll = 0;
+            }
+            KeyCode::Char('r') => {
+                *self = Self {
+                    errors_only: self.errors_only,
+                    ..Self::load()
+                };
+            }
+            KeyCode::Esc | KeyCode::Char('q') => return false,
+            _ => {}
+        }
+
+        true
+    }
+}
@@ -1,11 +1,16 @@
 use {
     crate::{
-        action::{Action, TuiAction},
+        action::{is_lossless, Action, TuiAction},
+        clipboard,
+        keymap::Mode,
         logger::*,
+        marks::{JumpList, Marks, Position},
+        registers::{Register, Registers},
+        settings::Clipboard,
     },
     glues_core::{
         data::{Directory, Note},
-        state::notebook::DirectoryItem,
+        state::notebook::{DirectoryItem, SearchDirection, Substitution, VisualKind},
         types::{Id, NoteId},
         NotebookEvent,
     },
@@ -14,7 +19,9 @@ use {
         text::Line,
         widgets::ListState,
     },
-    tui_textarea::TextArea,
+    regex::Regex,
+    std::ops::{Range, RangeInclusive},
+    tui_textarea::{CursorMove, TextArea},
 };
 
 pub const REMOVE_NOTE: &str = "Remove note";
@@ -45,6 +52,8 @@ pub enum ContextState {
     EditorNormalMode { idle: bool },
     EditorVisualMode,
     EditorInsertMode,
+    EditorSearchMode,
+    EditorCommandMode,
 }
 
 impl ContextState {
@@ -54,12 +63,28 @@ impl ContextState {
             ContextState::EditorNormalMode { .. }
                 | ContextState::EditorInsertMode
                 | ContextState::EditorVisualMode
+                | ContextState::EditorSearchMode
+                | ContextState::EditorCommandMode
         )
     }
+
+    pub fn keymap_mode(&self) -> Mode {
+        match self {
+            ContextState::NoteTreeBrowsing | ContextState::NoteTreeNumbering => Mode::Browser,
+            ContextState::NoteActionsDialog | ContextState::DirectoryActionsDialog => Mode::Actions,
+            ContextState::EditorNormalMode { .. }
+            | ContextState::EditorSearchMode
+            | ContextState::EditorCommandMode => Mode::Normal,
+            ContextState::EditorVisualMode => Mode::Visual,
+            ContextState::EditorInsertMode => Mode::Insert,
+        }
+    }
 }
 
 pub struct NotebookContext {
     pub state: ContextState,
+    /// Locator of the opened notebook, `None` for notebooks that are not persisted.
+    pub locator: Option<String>,
 
     // note tree
     pub tree_state: ListState,
@@ -76,20 +101,98 @@ pub struct NotebookContext {
     pub tab_index: Option<usize>,
     pub show_line_number: bool,
     pub show_browser: bool,
-    pub line_yanked: bool,
-    pub yank: Option<String>,
+    pub registers: Registers,
+    /// Selected with `"` for the next yank, delete or paste.
+    pub register: Option<char>,
+    pub clipboard: Clipboard,
+    /// Where visual mode started; only charwise selections are shown by the editor itself.
+    pub visual: Option<Visual>,
+    /// Copied to the other lines of the block once insert mode is left.
+    pub block_insert: Option<BlockInsert>,
+    /// Characters written over in replace mode, put back by Backspace.
+    /// `None` stands for one typed past the end of the line.
+    pub overwritten: Vec<Option<char>>,
+    /// Rows of text the editor showed in the last frame, for `H`, `M`, `L` and scrolling.
+    pub editor_height: usize,
+    pub marks: Marks,
+    pub jumps: JumpList,
+
+    // search
+    pub command_line: Option<CommandLine>,
+    pub search: Option<Search>,
+    pub highlight: bool,
+}
+
+/// Prompt line shown in place of the status bar, e.g. `/pattern` or `:w`.
+pub struct CommandLine {
+    pub prefix: char,
+    pub widget: TextArea<'static>,
+    /// Cursor of the editor when the prompt was opened.
+    pub origin: (usize, usize),
+}
+
+impl CommandLine {
+    pub fn text(&self) -> &str {
+        self.widget
+            .lines()
+            .first()
+            .map(String::as_str)
+            .unwrap_or_default()
+    }
+}
+
+/// The last search pattern, shared by all tabs.
+pub struct Search {
+    pub pattern: String,
+    pub direction: SearchDirection,
+}
+
+#[derive(Clone, Copy)]
+pub struct Visual {
+    pub kind: VisualKind,
+    pub anchor: (usize, usize),
+}
+
+impl Visual {
+    /// Top left and bottom right corners of the block between the anchor and the cursor.
+    pub fn block(&self, cursor: (usize, usize)) -> ((usize, usize), (usize, usize)) {
+        let (row, col) = self.anchor;
+
+        (
+            (row.min(cursor.0), col.min(cursor.1)),
+            (row.max(cursor.0), col.max(cursor.1)),
+        )
+    }
+}
+
+/// Text typed on the first line of a blockwise `I` or `A`.
+pub struct BlockInsert {
+    pub row: usize,
+    pub col: usize,
+    /// Length of the line and number of lines before typing, to find what was typed.
+    pub len: usize,
+    pub lines: usize,
+    /// The other lines of the block.
+    pub rows: RangeInclusive<usize>,
+    /// Whether lines shorter than the block are padded with spaces, as `A` does, or skipped.
+    pub pad: bool,
 }
 
 pub struct EditorTab {
     pub note: Note,
     pub editor: TextArea<'static>,
     pub dirty: bool,
+    /// The first row on screen to restore, kept until the editor has a viewport to scroll.
+    pub pending_scroll: Option<usize>,
+    /// The first row and column on screen, which `TextArea` keeps to itself, as of the last frame.
+    pub viewport: (usize, usize),
 }
 
 impl Default for NotebookContext {
     fn default() -> Self {
         Self {
             state: ContextState::NoteTreeBrowsing,
+            locator: None,
             tree_state: ListState::default().with_selected(Some(0)),
             tree_items: vec![],
 
@@ -100,8 +203,19 @@ impl Default for NotebookContext {
             tab_index: None,
             show_line_number: true,
             show_browser: true,
-            line_yanked: false,
-            yank: None,
+            registers: Registers::default(),
+            register: None,
+            clipboard: Clipboard::Auto,
+            visual: None,
+            block_insert: None,
+            overwritten: Vec::new(),
+            editor_height: 0,
+            marks: Marks::default(),
+            jumps: JumpList::default(),
+
+            command_line: None,
+            search: None,
+            highlight: false,
         }
     }
 }
@@ -113,20 +227,45 @@ impl NotebookContext {
             .map(|t| &t.note)
     }
 
-    pub fn get_editor(&self) -> &TextArea<'static> {
-        &self
-            .tab_index
+    pub fn get_tab(&self) -> &EditorTab {
+        self.tab_index
             .and_then(|i| self.tabs.get(i))
             .log_expect("no opened note")
-            .editor
     }
 
-    pub fn get_editor_mut(&mut self) -> &mut TextArea<'static> {
-        &mut self
-            .tab_index
+    pub fn get_tab_mut(&mut self) -> &mut EditorTab {
+        self.tab_index
             .and_then(|i| self.tabs.get_mut(i))
             .log_expect("no opened note")
-            .editor
+    }
+
+    pub fn get_editor(&self) -> &TextArea<'static> {
+        &self.get_tab().editor
+    }
+
+    pub fn get_editor_mut(&mut self) -> &mut TextArea<'static> {
+        &mut self.get_tab_mut().editor
+    }
+
+    /// Rows of the opened note on screen in the last frame, for `H`, `M` and `L`.
+    pub fn screen_rows(&self) -> Range<usize> {
+        let (top, _) = self.get_tab().viewport;
+
+        top..top + self.editor_height
+    }
+
+    /// Where the cursor is, to be marked or jumped back to.
+    pub fn position(&self) -> Position {
+        Position {
+            note: self.get_opened_note().log_expect("no opened note").clone(),
+            cursor: self.get_editor().cursor(),
+        }
+    }
+
+    /// Remembers the cursor before a jump, for `Ctrl+o`.
+    pub fn push_jump(&mut self) {
+        let position = self.position();
+        self.jumps.push(position);
     }
 
     pub fn mark_dirty(&mut self) {
@@ -215,22 +354,240 @@ impl NotebookContext {
                 note,
                 editor: TextArea::from(content.lines()),
                 dirty: false,
+                pending_scroll: None,
+                viewport: (0, 0),
             };
             self.tabs.push(tab);
             self.tab_index = Some(self.tabs.len() - 1);
         }
 
         self.apply_yank();
+        self.apply_search();
     }
 
+    /// Keeps the yank of the editor in sync with the unnamed register, for Ctrl+y in insert mode.
     pub fn apply_yank(&mut self) {
-        if let Some(yank) = self.yank.clone() {
-            self.get_editor_mut().set_yank_text(yank);
+        if let Some(register) = self.registers.get(None) {
+            let text = register.text.clone();
+            self.get_editor_mut().set_yank_text(text);
         }
     }
 
-    pub fn update_yank(&mut self) {
-        self.yank = Some(self.get_editor().yank_text());
+    /// Stores what the editor has just copied.
+    pub fn store_yank(&mut self, linewise: bool) {
+        let text = self.get_editor().yank_text();
+        let name = self.register.take();
+        self.copy_to_clipboard(name, &text, linewise);
+
+        self.registers.yank(name, Register { text, linewise });
+        self.apply_yank();
+    }
+
+    /// Stores what the editor has just cut.
+    pub fn store_delete(&mut self, linewise: bool) {
+        let text = self.get_editor().yank_text();
+        let name = self.register.take();
+        self.copy_to_clipboard(name, &text, linewise);
+
+        self.registers.delete(name, Register { text, linewise });
+        self.apply_yank();
+    }
+
+    fn copy_to_clipboard(&self, name: Option<char>, text: &str, linewise: bool) {
+        if !matches!(name, Some('+' | '*')) {
+            return;
+        }
+
+        // a trailing newline tells other programs, and paste below, that whole lines were copied
+        if linewise {
+            clipboard::copy(self.clipboard, &format!("{text}\n"));
+        } else {
+            clipboard::copy(self.clipboard, text);
+        }
+    }
+
+    /// Loads the selected register into the editor to be pasted, returning whether it is linewise.
+    pub fn load_register(&mut self) -> Option<bool> {
+        let name = self.register.take();
+        let pasted = match name {
+            Some('+' | '*') => clipboard::paste(self.clipboard),
+            _ => None,
+        };
+
+        let Register { text, linewise } = match pasted {
+            Some(text) => match text.strip_suffix('\n') {
+                Some(text) => Register {
+                    text: text.to_owned(),
+                    linewise: true,
+                },
+                None => Register {
+                    text,
+                    linewise: false,
+                },
+            },
+            None => self.registers.get(name)?.clone(),
+        };
+        self.get_editor_mut().set_yank_text(text);
+
+        Some(linewise)
+    }
+
+    /// Pastes into the command line or, in insert mode, into the editor.
+    pub fn paste(&mut self, text: &str) {
+        if let Some(command_line) = self.command_line.as_mut() {
+            let line = text.lines().next().unwrap_or_default();
+            command_line.widget.insert_str(line);
+
+            match command_line.prefix {
+                '/' => self.preview_search(SearchDirection::Forward),
+                '?' => self.preview_search(SearchDirection::Backward),
+                _ => {}
+            }
+        } else if self.state == ContextState::EditorInsertMode {
+            self.get_editor_mut()
+                .insert_str(text.replace("\r\n", "\n").replace('\r', "\n"));
+            self.mark_dirty();
+        }
+    }
+
+    /// Highlights the last search pattern in the current tab, unless it has been cleared.
+    pub fn apply_search(&mut self) {
+        let pattern = match &self.search {
+            Some(Search { pattern, .. }) if self.highlight => search_regex(pattern),
+            _ => String::new(),
+        };
+
+        let _ = self.get_editor_mut().set_search_pattern(pattern);
+    }
+
+    pub fn open_command_line(&mut self, prefix: char) {
+        self.command_line = Some(CommandLine {
+            prefix,
+            widget: TextArea::default(),
+            origin: self.get_editor().cursor(),
+        });
+    }
+
+    /// Moves to the first match of the typed pattern, as it is being typed.
+    fn preview_search(&mut self, direction: SearchDirection) {
+        let command_line = self
+            .command_line
+            .as_ref()
+            .log_expect("command line must be some");
+        let pattern = search_regex(command_line.text());
+        let (row, col) = command_line.origin;
+
+        let editor = self.get_editor_mut();
+        editor.move_cursor(CursorMove::Jump(row as u16, col as u16));
+        if pattern.is_empty() || editor.set_search_pattern(pattern).is_err() {
+            let _ = editor.set_search_pattern("");
+            return;
+        }
+
+        search_step(editor, direction);
+    }
+
+    /// Returns `false` when the pattern does not match anywhere.
+    pub fn confirm_search(&mut self, direction: SearchDirection, pattern: String) -> bool {
+        let command_line = self
+            .command_line
+            .take()
+            .log_expect("command line must be some");
+        self.search = Some(Search { pattern, direction });
+
+        self.highlight = true;
+        self.apply_search();
+
+        let (row, col) = command_line.origin;
+        self.get_editor_mut()
+            .move_cursor(CursorMove::Jump(row as u16, col as u16));
+        self.push_jump();
+
+        search_step(self.get_editor_mut(), direction)
+    }
+
+    pub fn cancel_search(&mut self) {
+        let command_line = self
+            .command_line
+            .take()
+            .log_expect("command line must be some");
+        let (row, col) = command_line.origin;
+
+        self.get_editor_mut()
+            .move_cursor(CursorMove::Jump(row as u16, col as u16));
+        self.apply_search();
+    }
+
+    /// Repeats the last search `n` times, in the opposite direction when `reverse` is set.
+    pub fn search_next(&mut self, n: usize, reverse: bool) -> bool {
+        let direction = match (&self.search, reverse) {
+            (Some(search), false) => search.direction,
+            (Some(search), true) => match search.direction {
+                SearchDirection::Forward => SearchDirection::Backward,
+                SearchDirection::Backward => SearchDirection::Forward,
+            },
+            (None, _) => return false,
+        };
+
+        let editor = self.get_editor_mut();
+        (0..n).all(|_| search_step(editor, direction))
+    }
+
+    pub fn clear_search(&mut self) {
+        self.highlight = false;
+        self.apply_search();
+    }
+
+    /// Returns the number of substitutions and of the lines changed by them.
+    pub fn substitute(&mut self, substitution: &Substitution) -> Result<(usize, usize), String> {
+        let pattern = match (substitution.pattern.as_str(), &self.search) {
+            ("", Some(search)) => search.pattern.clone(),
+            ("", None) => return Err("No previous search pattern".to_owned()),
+            (pattern, _) => pattern.to_owned(),
+        };
+        let pattern = if substitution.ignore_case {
+            format!("(?i){pattern}")
+        } else {
+            search_regex(&pattern)
+        };
+        let regex = Regex::new(&pattern).map_err(|error| format!("Invalid pattern: {error}"))?;
+        let replacement = substitute_replacement(&substitution.replacement);
+
+        let editor = self.get_editor_mut();
+        let last = editor.lines().len() - 1;
+        let (start, end) = substitution.range.resolve(editor.cursor().0, last);
+
+        // rewriting a line goes through delete_str, which would replace the yank
+        let yank = editor.yank_text();
+        let (mut count, mut lines) = (0, 0);
+        for row in start..=end {
+            let line = editor.lines()[row].clone();
+            let matches = regex.find_iter(&line).count();
+            if matches == 0 {
+                continue;
+            }
+
+            let replaced = if substitution.global {
+                count += matches;
+                regex.replace_all(&line, &replacement)
+            } else {
+                count += 1;
+                regex.replace(&line, &replacement)
+            };
+            lines += 1;
+
+            editor.move_cursor(CursorMove::Jump(row as u16, 0));
+            editor.delete_str(line.chars().count());
+            editor.insert_str(replaced);
+            editor.move_cursor(CursorMove::Head);
+        }
+        editor.set_yank_text(yank);
+
+        if lines > 0 {
+            self.mark_dirty();
+        }
+
+        Ok((count, lines))
     }
 
     pub fn consume(&mut self, input: &Input) -> Action {
@@ -245,6 +602,8 @@ impl NotebookContext {
             ContextState::EditorNormalMode { idle } => self.consume_on_editor_normal(input, idle),
             ContextState::EditorVisualMode => Action::PassThrough,
             ContextState::EditorInsertMode => self.consume_on_editor_insert(input),
+            ContextState::EditorSearchMode => self.consume_on_editor_search(input),
+            ContextState::EditorCommandMode => self.consume_on_editor_command(input),
             ContextState::NoteActionsDialog => self.consume_on_note_actions(code),
             ContextState::DirectoryActionsDialog => self.consume_on_directory_actions(code),
         }
@@ -305,6 +664,11 @@ impl NotebookContext {
                     Action::PassThrough
                 }
             },
+            KeyCode::Char('q') => TuiAction::SaveAndConfirm {
+                message: "Close this notebook and open another one?".to_owned(),
+                action: Box::new(TuiAction::CloseNotebook.into()),
+            }
+            .into(),
             KeyCode::Esc => TuiAction::Confirm {
                 message: "Do you want to quit?".to_owned(),
                 action: Box::new(TuiAction::Quit.into()),
@@ -315,20 +679,25 @@ impl NotebookContext {
     }
 
     fn consume_on_editor_normal(&mut self, input: &Input, idle: bool) -> Action {
-        let code = match input {
-            Input::Key(key) => key.code,
+        let (code, modifiers) = match input {
+            Input::Key(key) => (key.code, key.modifiers),
             _ => return Action::None,
         };
 
         match code {
-            KeyCode::Esc if idle => TuiAction::SaveAndConfirm {
+            KeyCode::Char('s') if idle && modifiers == KeyModifiers::CONTROL => {
+                TuiAction::Save.into()
+            }
+            KeyCode::Char('s') if idle && modifiers == KeyModifiers::ALT => {
+                TuiAction::SaveAll.into()
+            }
+            KeyCode::Esc if idle && !self.highlight => TuiAction::SaveAndConfirm {
                 message: "Do you want to quit?".to_owned(),
                 action: Box::new(TuiAction::Quit.into()),
             }
             .into(),
-            KeyCode::Char('n') if idle => {
+            KeyCode::Char('n') if idle && !self.highlight => {
                 self.show_browser = true;
-                self.update_yank();
 
                 TuiAction::SaveAndPassThrough.into()
             }
@@ -347,16 +716,106 @@ impl NotebookContext {
                 ..
             }) => TuiAction::ShowEditorKeymap.into(),
             Input::Key(KeyEvent {
-                code: KeyCode::Char('c' | 'x' | 'w' | 'k' | 'j'),
+                code: KeyCode::Char('s'),
                 modifiers: KeyModifiers::CONTROL,
                 ..
+            }) => TuiAction::Save.into(),
+            Input::Key(KeyEvent {
+                code: KeyCode::Char('s'),
+                modifiers: KeyModifiers::ALT,
+                ..
+            }) => TuiAction::SaveAll.into(),
+            // keys the core can take are recorded there for `.`, and come back to be typed;
+            // those it would lose a modifier of, such as Ctrl+Left, are typed right away
+            _ if is_lossless(input) => Action::PassThrough,
+            _ => {
+                if self.get_editor_mut().input(input.clone()) {
+                    self.mark_dirty();
+                }
+
+                Action::None
+            }
+        }
+    }
+
+    fn consume_on_editor_search(&mut self, input: &Input) -> Action {
+        let command_line = self
+            .command_line
+            .as_mut()
+            .log_expect("command line must be some");
+        let direction = match command_line.prefix {
+            '?' => SearchDirection::Backward,
+            _ => SearchDirection::Forward,
+        };
+
+        match input {
+            Input::Key(KeyEvent {
+                code: KeyCode::Esc, ..
+            }) => Action::PassThrough,
+            Input::Key(KeyEvent {
+                code: KeyCode::Enter,
+                ..
             }) => {
-                self.line_yanked = false;
-                self.get_editor_mut().input(input.clone());
+                let pattern = match (command_line.text(), &self.search) {
+                    ("", Some(search)) => search.pattern.clone(),
+                    ("", None) => {
+                        return TuiAction::Alert("No previous search pattern".to_owned()).into()
+                    }
+                    (pattern, _) => pattern.to_owned(),
+                };
+
+                match self
+                    .get_editor_mut()
+                    .set_search_pattern(search_regex(&pattern))
+                {
+                    Ok(()) => Action::Dispatch(NotebookEvent::Search(pattern).into()),
+                    Err(error) => {
+                        TuiAction::Alert(format!("Invalid search pattern: {error}")).into()
+                    }
+                }
+            }
+            Input::Key(KeyEvent {
+                code: KeyCode::Backspace,
+                ..
+            }) if command_line.text().is_empty() => {
+                Action::Dispatch(glues_core::KeyEvent::Esc.into())
+            }
+            _ => {
+                command_line.widget.input(input.clone());
+                self.preview_search(direction);
+
                 Action::None
             }
+        }
+    }
+
+    fn consume_on_editor_command(&mut self, input: &Input) -> Action {
+        let command_line = self
+            .command_line
+            .as_mut()
+            .log_expect("command line must be some");
+
+        match input {
+            Input::Key(KeyEvent {
+                code: KeyCode::Esc, ..
+            }) => Action::PassThrough,
+            Input::Key(KeyEvent {
+                code: KeyCode::Enter,
+                ..
+            }) => {
+                let line = command_line.text().to_owned();
+
+                Action::Dispatch(NotebookEvent::RunCommand(line).into())
+            }
+            Input::Key(KeyEvent {
+                code: KeyCode::Backspace,
+                ..
+            }) if command_line.text().is_empty() => {
+                Action::Dispatch(glues_core::KeyEvent::Esc.into())
+            }
             _ => {
-                self.get_editor_mut().input(input.clone());
+                command_line.widget.input(input.clone());
+
                 Action::None
             }
         }
@@ -460,6 +919,23 @@ pub enum TreeItem {
     },
 }
 
+/// Smart-case: patterns without uppercase letters match case-insensitively.
+fn search_regex(pattern: &str) -> String {
+    if pattern.is_empty() || pattern.chars().any(char::is_uppercase) {
+        pattern.to_owned()
+    } else {
+        format!("(?i){pattern}")
+    }
+}
+
+/// Returns `false` when there is no match to move to.
+fn search_step(editor: &mut TextArea, direction: SearchDirection) -> bool {
+    match direction {
+        SearchDirection::Forward => editor.search_forward(false),
+        SearchDirection::Backward => editor.search_back(false),
+    }
+}
+
 fn flatten(directory_item: &DirectoryItem, depth: usize) -> Vec<TreeItem> {
     let mut items = vec![TreeItem::Directory {
         value: directory_item.directory.clone(),
@@ -482,3 +958,25 @@ fn flatten(directory_item: &DirectoryItem, depth: usize) -> Vec<TreeItem> {
 
     items
 }
+
+/// Translates `&` and `\1` of a Vim replacement into the syntax of `regex`.
+fn substitute_replacement(replacement: &str) -> String {
+    let mut translated = String::new();
+    let mut chars = replacement.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '&' => translated.push_str("${0}"),
+            '$' => translated.push_str("$$"),
+            '\\' => match chars.next() {
+                Some(n @ '0'..='9') => translated.push_str(&format!("${{{n}}}")),
+                Some('$') => translated.push_str("$$"),
+                Some(c) => translated.push(c),
+                None => translated.push('\\'),
+            },
+            c => translated.push(c),
+        }
+    }
+
+    translated
+}
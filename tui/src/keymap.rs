@@ -0,0 +1,241 @@
+use {
+    ratatui::{
+        crossterm::event::{Event as Input, KeyCode, KeyEvent, KeyModifiers},
+        text::{Line, Span},
+    },
+    std::{
+        collections::{HashMap, HashSet},
+        fmt,
+    },
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Mode {
+    Entry,
+    Browser,
+    Normal,
+    Visual,
+    Insert,
+    Actions,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Chord {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl Chord {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let invalid = || format!("invalid key '{value}'");
+        let (prefix, key) = match value.strip_suffix("++") {
+            Some(prefix) => (Some(prefix), "+"),
+            None => match value.rsplit_once('+') {
+                Some((prefix, key)) if !key.is_empty() => (Some(prefix), key),
+                _ => (None, value),
+            },
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        for modifier in prefix.into_iter().flat_map(|prefix| prefix.split('+')) {
+            modifiers |= match modifier.to_lowercase().as_str() {
+                "ctrl" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                _ => return Err(invalid()),
+            };
+        }
+
+        let code = match key {
+            "Esc" => KeyCode::Esc,
+            "Enter" => KeyCode::Enter,
+            "Tab" => KeyCode::Tab,
+            "Backspace" => KeyCode::Backspace,
+            "Delete" => KeyCode::Delete,
+            "Space" => KeyCode::Char(' '),
+            "Left" => KeyCode::Left,
+            "Right" => KeyCode::Right,
+            "Up" => KeyCode::Up,
+            "Down" => KeyCode::Down,
+            "Home" => KeyCode::Home,
+            "End" => KeyCode::End,
+            "PageUp" => KeyCode::PageUp,
+            "PageDown" => KeyCode::PageDown,
+            _ => {
+                let mut chars = key.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => return Err(invalid()),
+                }
+            }
+        };
+
+        Ok(Self { code, modifiers })
+    }
+
+    fn from_key(key: &KeyEvent) -> Self {
+        // shift is already reflected in the character itself
+        let modifiers = match key.code {
+            KeyCode::Char(_) => key.modifiers - KeyModifiers::SHIFT,
+            _ => key.modifiers,
+        };
+
+        Self {
+            code: key.code,
+            modifiers,
+        }
+    }
+}
+
+impl fmt::Display for Chord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{c}"),
+            KeyCode::Esc => write!(f, "Esc"),
+            KeyCode::Enter => write!(f, "Enter"),
+            KeyCode::Tab => write!(f, "Tab"),
+            KeyCode::Backspace => write!(f, "Backspace"),
+            KeyCode::Delete => write!(f, "Delete"),
+            KeyCode::Left => write!(f, "Left"),
+            KeyCode::Right => write!(f, "Right"),
+            KeyCode::Up => write!(f, "Up"),
+            KeyCode::Down => write!(f, "Down"),
+            KeyCode::Home => write!(f, "Home"),
+            KeyCode::End => write!(f, "End"),
+            KeyCode::PageUp => write!(f, "PageUp"),
+            KeyCode::PageDown => write!(f, "PageDown"),
+            code => write!(f, "{code:?}"),
+        }
+    }
+}
+
+/// Translates pressed keys into the default keys whose actions they trigger.
+/// A default key that was moved to another key is unbound, unless it is remapped itself.
+#[derive(Clone, Default)]
+struct Layer {
+    forward: HashMap<Chord, Chord>,
+    reverse: HashMap<Chord, Chord>,
+    unbound: HashSet<Chord>,
+}
+
+impl Layer {
+    fn new(bindings: &HashMap<String, String>) -> Result<Self, String> {
+        let mut layer = Self::default();
+
+        for (key, target) in bindings {
+            let key = Chord::parse(key)?;
+            let target = Chord::parse(target)?;
+
+            layer.forward.insert(key, target);
+            if layer.reverse.insert(target, key).is_some() {
+                return Err(format!("more than one key is bound to '{target}'"));
+            }
+        }
+
+        layer.unbound = layer
+            .reverse
+            .keys()
+            .filter(|target| !layer.forward.contains_key(target))
+            .copied()
+            .collect();
+
+        Ok(layer)
+    }
+
+    fn label(&self, chord: Chord) -> String {
+        match self.reverse.get(&chord) {
+            Some(key) => key.to_string(),
+            None if self.forward.contains_key(&chord) => "-".to_owned(),
+            None => chord.to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct Keymap {
+    layers: HashMap<Mode, Layer>,
+}
+
+impl Keymap {
+    pub fn bind(&mut self, mode: Mode, bindings: &HashMap<String, String>) -> Result<(), String> {
+        self.layers.insert(mode, Layer::new(bindings)?);
+
+        Ok(())
+    }
+
+    /// Returns `None` when the pressed key has been unbound in the given mode.
+    pub fn translate(&self, mode: Mode, input: Input) -> Option<Input> {
+        let key = match (&input, self.layers.get(&mode)) {
+            (Input::Key(key), Some(layer)) => {
+                let chord = Chord::from_key(key);
+                if layer.unbound.contains(&chord) {
+                    return None;
+                }
+
+                match layer.forward.get(&chord) {
+                    Some(Chord { code, modifiers }) => KeyEvent {
+                        code: *code,
+                        modifiers: *modifiers,
+                        ..*key
+                    },
+                    None => return Some(input),
+                }
+            }
+            _ => return Some(input),
+        };
+
+        Some(Input::Key(key))
+    }
+
+    /// Rewrites key hints such as `[j|k] Down | Up` to show the keys bound in the given mode.
+    pub fn relabel(&self, mode: Mode, text: &str) -> String {
+        let layer = match self.layers.get(&mode) {
+            Some(layer) if !layer.forward.is_empty() => layer,
+            _ => return text.to_owned(),
+        };
+
+        let mut output = String::with_capacity(text.len());
+        let mut rest = text;
+
+        while let Some(start) = rest.find('[') {
+            let end = match rest[start..].find(']') {
+                Some(end) => start + end,
+                None => break,
+            };
+
+            let keys = rest[start + 1..end]
+                .split('|')
+                .map(|key| {
+                    Chord::parse(key)
+                        .map(|chord| layer.label(chord))
+                        .unwrap_or_else(|_| key.to_owned())
+                })
+                .collect::<Vec<_>>()
+                .join("|");
+
+            output.push_str(&rest[..start]);
+            output.push_str(&format!("[{keys}]"));
+            rest = &rest[end + 1..];
+        }
+
+        output.push_str(rest);
+        output
+    }
+
+    pub fn relabel_line(&self, mode: Mode, line: Line<'static>) -> Line<'static> {
+        let spans = line
+            .spans
+            .into_iter()
+            .map(|span| Span::styled(self.relabel(mode, &span.content), span.style))
+            .collect::<Vec<_>>();
+
+        Line::from(spans).style(line.style)
+    }
+}
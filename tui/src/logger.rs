@@ -1,44 +1,151 @@
 use {
-    super::config::get_glue,
-    gluesql::core::ast_builder::{table, text, Execute},
-    std::fmt::{Debug, Display},
+    crate::config,
+    gluesql::core::chrono::{Local, NaiveDateTime},
+    log::{Level, LevelFilter, Log, Metadata, Record},
+    std::{
+        fmt::{Debug, Display},
+        fs::{self, File, OpenOptions},
+        io::Write,
+        path::PathBuf,
+        str::FromStr,
+        sync::Mutex,
+    },
 };
 
-pub async fn init() {
-    let mut glue = get_glue();
-
-    table("logs")
-        .drop_table_if_exists()
-        .execute(&mut glue)
-        .await
-        .unwrap();
-
-    table("logs")
-        .create_table_if_not_exists()
-        .add_column("timestamp TIMESTAMP DEFAULT NOW()")
-        .add_column("message TEXT")
-        .execute(&mut glue)
-        .await
-        .unwrap();
+/// Sets the verbosity: `off`, `error`, `warn`, `info`, `debug` or `trace`.
+pub const LOG_ENV: &str = "GLUES_LOG";
+
+/// The current session plus this many previous ones are kept.
+pub const MAX_ROTATIONS: usize = 4;
+
+pub const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.3f";
+const TIMESTAMP_LEN: usize = "2000-01-01 00:00:00.000".len();
+
+pub struct LogEntry {
+    pub timestamp: String,
+    pub level: Level,
+    pub message: String,
 }
 
-pub async fn log(message: &str) {
-    let mut glue = get_glue();
+struct Logger {
+    file: Mutex<File>,
+}
 
-    table("logs")
-        .insert()
-        .columns("message")
-        .values(vec![vec![text(message)]])
-        .execute(&mut glue)
-        .await
-        .unwrap();
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        // dependencies log through the same facade, only keep our own records
+        metadata.target().starts_with("glues") && metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let timestamp = Local::now().format(TIMESTAMP_FORMAT);
+        let mut file = self.file.lock().unwrap_or_else(|error| error.into_inner());
+        let _ = writeln!(
+            file,
+            "{timestamp} {:<5} {}: {}",
+            record.level(),
+            record.target(),
+            record.args()
+        );
+    }
+
+    fn flush(&self) {
+        let _ = self
+            .file
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .flush();
+    }
+}
+
+pub fn dir() -> PathBuf {
+    config::dir().join("logs")
 }
 
-#[macro_export]
-macro_rules! log {
-    ($($arg:tt)*) => {
-        log(&format!($($arg)*)).await;
+/// Log file of the `n`th previous session, `0` being the current one.
+pub fn path(n: usize) -> PathBuf {
+    match n {
+        0 => dir().join("glues.log"),
+        n => dir().join(format!("glues.{n}.log")),
+    }
+}
+
+/// Reads the entries of the `n`th previous session, oldest first.
+/// Lines that do not start a new record, such as panic messages, are appended to the previous one.
+pub fn read(n: usize) -> Vec<LogEntry> {
+    let content = fs::read_to_string(path(n)).unwrap_or_default();
+    let mut entries: Vec<LogEntry> = Vec::new();
+
+    for line in content.lines() {
+        let record = line
+            .get(..TIMESTAMP_LEN)
+            .zip(line.get(TIMESTAMP_LEN..))
+            .filter(|(timestamp, _)| {
+                NaiveDateTime::parse_from_str(timestamp, TIMESTAMP_FORMAT).is_ok()
+            })
+            .and_then(|(timestamp, rest)| {
+                let (level, message) = rest.trim_start().split_once(' ')?;
+
+                Some((timestamp, Level::from_str(level).ok()?, message))
+            });
+
+        match (record, entries.last_mut()) {
+            (Some((timestamp, level, message)), _) => entries.push(LogEntry {
+                timestamp: timestamp.to_owned(),
+                level,
+                message: message.trim_start().to_owned(),
+            }),
+            (None, Some(entry)) => {
+                entry.message.push('\n');
+                entry.message.push_str(line);
+            }
+            (None, None) => {}
+        }
+    }
+
+    entries
+}
+
+pub fn init() {
+    fs::create_dir_all(dir()).expect("failed to create log directory");
+
+    for n in (0..MAX_ROTATIONS).rev() {
+        let _ = fs::rename(path(n), path(n + 1));
+    }
+
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path(0))
+        .expect("failed to open log file");
+
+    let (filter, invalid) = match std::env::var(LOG_ENV) {
+        Ok(level) => match LevelFilter::from_str(&level) {
+            Ok(filter) => (filter, None),
+            Err(_) => (LevelFilter::Info, Some(level)),
+        },
+        Err(_) => (LevelFilter::Info, None),
     };
+
+    log::set_boxed_logger(Box::new(Logger {
+        file: Mutex::new(file),
+    }))
+    .expect("logger is already set");
+    log::set_max_level(filter);
+
+    if let Some(level) = invalid {
+        log::warn!("invalid {LOG_ENV} value: {level}");
+    }
+
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        log::error!("{info}");
+        hook(info);
+    }));
 }
 
 pub trait LogExpectExt<V> {
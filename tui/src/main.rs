@@ -1,21 +1,42 @@
+#[macro_use]
+extern crate log;
+
 mod action;
+mod clipboard;
+mod config;
 pub mod context;
-#[macro_use]
+mod keymap;
+mod locator;
 mod logger;
-mod config;
+mod marks;
+mod registers;
+mod session;
+mod settings;
+mod swap;
+mod theme;
 mod transitions;
 mod views;
 
 use {
     action::Action,
-    color_eyre::Result,
+    color_eyre::{eyre::eyre, Result},
     context::Context,
-    glues_core::Glues,
+    futures::FutureExt,
+    glues_core::{
+        state::{
+            notebook::{InnerState, VimNormalState, VimVisualState},
+            GetInner, NotebookState,
+        },
+        EntryEvent, Glues,
+    },
     logger::*,
     ratatui::{
         crossterm::{
             self,
-            event::{Event as Input, KeyCode, KeyEvent as CKeyEvent, KeyEventKind, KeyModifiers},
+            event::{
+                DisableBracketedPaste, EnableBracketedPaste, Event as Input, KeyCode,
+                KeyEvent as CKeyEvent, KeyEventKind, KeyModifiers,
+            },
         },
         layout::{
             Constraint::{Length, Percentage},
@@ -23,37 +44,136 @@ use {
         },
         DefaultTerminal, Frame,
     },
-    std::time::Duration,
+    settings::{Autosave, Settings},
+    std::{
+        io,
+        panic::AssertUnwindSafe,
+        time::{Duration, Instant, SystemTime},
+    },
 };
 
 #[tokio::main]
 async fn main() -> Result<()> {
     config::init().await;
-    logger::init().await;
     color_eyre::install()?;
+    logger::init();
+
+    let settings = settings::load().map_err(|message| eyre!(message))?;
+    let entry = match std::env::args().nth(1).as_deref() {
+        Some("-h" | "--help") => {
+            println!("{}", locator::USAGE);
+            return Ok(());
+        }
+        Some(locator) => locator::parse(locator)
+            .map(Some)
+            .map_err(|message| eyre!("{message}\n\n{}", locator::USAGE))?,
+        None => settings
+            .storage
+            .default
+            .as_deref()
+            .map(locator::parse)
+            .transpose()
+            .map_err(|message| eyre!(message))?,
+    };
+
+    info!("Hello");
 
-    log!("Hello");
+    let mut app = App::new(settings).await;
+    if let Some(event) = entry {
+        app.open(event).await;
+    }
 
     let terminal = ratatui::init();
-    let app_result = App::new().await.run(terminal).await;
+    crossterm::execute!(io::stdout(), EnableBracketedPaste)?;
+    let app_result = AssertUnwindSafe(app.run(terminal)).catch_unwind().await;
+    let _ = crossterm::execute!(io::stdout(), DisableBracketedPaste);
     ratatui::restore();
-    app_result
+
+    match app_result {
+        Ok(result) => result,
+        Err(panic) => {
+            app.write_swaps();
+            std::panic::resume_unwind(panic)
+        }
+    }
 }
 
 struct App {
     glues: Glues,
     context: Context,
+    settings_modified: Option<SystemTime>,
+    swapped_at: Instant,
+    /// Set by the autosave policy, the dirty tabs are saved once the input is handled.
+    save_requested: bool,
 }
 
 impl App {
-    async fn new() -> Self {
+    async fn new(settings: Settings) -> Self {
         let glues = Glues::new().await;
-        let context = Context::default();
+        let mut context = Context {
+            settings,
+            ..Context::default()
+        };
+        context.entry.recent = config::recent().await;
+        // loaded before anything can save them, as quitting from the entry screen does
+        context.notebook.registers = registers::load();
+
+        Self {
+            glues,
+            context,
+            settings_modified: settings::modified(),
+            swapped_at: Instant::now(),
+            save_requested: false,
+        }
+    }
 
-        Self { glues, context }
+    async fn reload_settings(&mut self) {
+        let modified = settings::modified();
+        if modified == self.settings_modified {
+            return;
+        }
+
+        self.settings_modified = modified;
+        match settings::load() {
+            Ok(settings) => {
+                info!("Settings reloaded");
+                self.context.notebook.clipboard = settings.editor.clipboard;
+                self.context.settings = settings;
+            }
+            Err(message) => {
+                error!("{message}");
+                self.context.alert = Some(message);
+            }
+        }
     }
 
-    async fn run(mut self, mut terminal: DefaultTerminal) -> Result<()> {
+    async fn open(&mut self, event: EntryEvent) {
+        info!("Opening {event}");
+
+        let recent = locator::format(&event);
+
+        match self.glues.dispatch(event.into()).await {
+            Ok(transition) => {
+                if let Some((kind, locator)) = &recent {
+                    config::touch_recent(kind, locator).await;
+                }
+
+                self.handle_transition(transition).await;
+
+                if let Some((_, locator)) = recent {
+                    self.restore_session(&locator).await;
+                    self.check_swaps(&locator).await;
+                    self.context.notebook.locator = Some(locator);
+                }
+            }
+            Err(error) => {
+                error!("{error}");
+                self.context.alert = Some(error.to_string());
+            }
+        }
+    }
+
+    async fn run(&mut self, mut terminal: DefaultTerminal) -> Result<()> {
         loop {
             if let Some((_, created_at)) = self.context.last_log {
                 if created_at.elapsed().log_unwrap().as_secs() > 5 {
@@ -63,7 +183,8 @@ impl App {
 
             terminal.draw(|frame| self.draw(frame))?;
 
-            if !crossterm::event::poll(Duration::from_millis(1500))? {
+            let interval = self.context.settings.editor.autosave_interval;
+            if !crossterm::event::poll(Duration::from_millis(interval))? {
                 let mut transitions = Vec::new();
                 {
                     let mut queue = self.glues.transition_queue.lock().log_unwrap();
@@ -77,11 +198,20 @@ impl App {
                     self.handle_transition(transition).await;
                 }
 
-                self.save().await;
+                self.write_swaps();
+                if self.context.settings.editor.autosave == Autosave::Idle {
+                    self.save().await;
+                }
+                self.reload_settings().await;
                 continue;
             }
 
             let input = crossterm::event::read()?;
+            if let Input::Paste(text) = input {
+                self.context.paste(&text);
+                self.write_swaps_if_due();
+                continue;
+            }
             if !matches!(
                 input,
                 Input::Key(CKeyEvent {
@@ -98,27 +228,73 @@ impl App {
                     modifiers: KeyModifiers::CONTROL,
                     ..
                 }) => {
+                    self.save().await;
+                    // notes which failed to save are kept in swap files
+                    self.write_swaps();
+                    self.save_session();
                     return Ok(());
                 }
                 _ => {
+                    let input = match self.reads_char() {
+                        true => input,
+                        false => match self.context.translate(input) {
+                            Some(input) => input,
+                            None => continue,
+                        },
+                    };
                     let action = self.context.consume(&input).await;
                     let quit = self.handle_action(action, input).await;
                     if quit {
                         return Ok(());
                     }
+
+                    if self.save_requested {
+                        self.save_requested = false;
+                        self.save().await;
+                    }
+
+                    self.write_swaps_if_due();
                 }
             }
         }
     }
 
+    /// Keys naming a find target, a replacement, a register, a macro or a mark are taken
+    /// as typed, not through the keymap.
+    fn reads_char(&self) -> bool {
+        let state: Option<&NotebookState> = self.glues.state.get_inner().ok();
+
+        matches!(
+            state.map(|state| state.inner_state),
+            Some(
+                InnerState::EditingNormalMode(
+                    VimNormalState::Find(..)
+                        | VimNormalState::Register
+                        | VimNormalState::ReplaceChar(_)
+                        | VimNormalState::RecordMacro
+                        | VimNormalState::PlayMacro(_)
+                        | VimNormalState::Mark
+                        | VimNormalState::JumpToMark(_)
+                ) | InnerState::EditingVisualMode(
+                    VimVisualState::Find(..) | VimVisualState::Register
+                )
+            )
+        )
+    }
+
     fn draw(&mut self, frame: &mut Frame) {
         let state = &self.glues.state;
         let context = &mut self.context;
         let vertical = Layout::vertical([Length(1), Percentage(100), Length(1)]);
         let [keymap, body, statusbar] = vertical.areas(frame.area());
 
-        views::keymap::draw(frame, keymap, state);
-        views::statusbar::draw(frame, statusbar, state);
+        views::keymap::draw(frame, keymap, state, context);
+        match context.notebook.command_line.as_mut() {
+            Some(command_line) => {
+                views::command_line::draw(frame, statusbar, command_line, &context.settings.theme)
+            }
+            None => views::statusbar::draw(frame, statusbar, state, &context.settings.theme),
+        }
         views::body::draw(frame, body, context);
         views::dialog::draw(frame, context);
     }
@@ -0,0 +1,183 @@
+use {
+    crate::{logger::*, App},
+    glues_core::{
+        data::Note,
+        state::{GetInner, NotebookState},
+        transition::{NotebookTransition, Transition},
+        types::NoteId,
+        NotebookEvent,
+    },
+    std::collections::HashMap,
+    tui_textarea::CursorMove,
+};
+
+/// Older jumps are dropped past this many, as Vim does.
+const JUMP_LIMIT: usize = 100;
+
+/// A cursor in a note, kept along with the note to reopen its tab.
+#[derive(Clone)]
+pub struct Position {
+    pub note: Note,
+    pub cursor: (usize, usize),
+}
+
+/// `a`-`z` are kept for each note, `A`-`Z` across the notebook.
+#[derive(Default)]
+pub struct Marks {
+    local: HashMap<(NoteId, char), (usize, usize)>,
+    global: HashMap<char, Position>,
+}
+
+impl Marks {
+    pub fn set(&mut self, name: char, position: Position) {
+        if name.is_ascii_uppercase() {
+            self.global.insert(name, position);
+        } else {
+            self.local.insert((position.note.id, name), position.cursor);
+        }
+    }
+
+    /// Looks `name` up for `note`, the note the cursor is in.
+    pub fn get(&self, name: char, note: &Note) -> Option<Position> {
+        if name.is_ascii_uppercase() {
+            return self.global.get(&name).cloned();
+        }
+
+        let cursor = *self.local.get(&(note.id.clone(), name))?;
+
+        Some(Position {
+            note: note.clone(),
+            cursor,
+        })
+    }
+
+    pub fn rename_note(&mut self, note: &Note) {
+        for position in self.global.values_mut() {
+            if position.note.id == note.id {
+                position.note = note.clone();
+            }
+        }
+    }
+
+    pub fn remove_note(&mut self, note_id: &NoteId) {
+        self.local.retain(|(id, _), _| id != note_id);
+        self.global
+            .retain(|_, position| &position.note.id != note_id);
+    }
+}
+
+/// Where jumps were made from, walked with `Ctrl+o` and `Ctrl+i`.
+#[derive(Default)]
+pub struct JumpList {
+    jumps: Vec<Position>,
+    /// `jumps.len()` while no older jump is being visited.
+    index: usize,
+}
+
+impl JumpList {
+    /// Keeps one entry for each line, the latest.
+    pub fn push(&mut self, position: Position) {
+        self.jumps
+            .retain(|jump| jump.note.id != position.note.id || jump.cursor.0 != position.cursor.0);
+        self.jumps.push(position);
+        if self.jumps.len() > JUMP_LIMIT {
+            self.jumps.remove(0);
+        }
+
+        self.index = self.jumps.len();
+    }
+
+    /// `current` is kept on leaving the newest entry, so that `Ctrl+i` comes back to it.
+    pub fn back(&mut self, current: Position, n: usize) -> Option<Position> {
+        if self.index >= self.jumps.len() {
+            self.push(current);
+            self.index = self.jumps.len() - 1;
+        }
+
+        self.index = self.index.checked_sub(n)?;
+
+        self.jumps.get(self.index).cloned()
+    }
+
+    pub fn forward(&mut self, n: usize) -> Option<Position> {
+        let index = self.index.saturating_add(n);
+        if index >= self.jumps.len() {
+            return None;
+        }
+
+        self.index = index;
+
+        self.jumps.get(index).cloned()
+    }
+
+    pub fn rename_note(&mut self, note: &Note) {
+        for jump in self.jumps.iter_mut() {
+            if jump.note.id == note.id {
+                jump.note = note.clone();
+            }
+        }
+    }
+
+    pub fn remove_note(&mut self, note_id: &NoteId) {
+        self.jumps.retain(|jump| &jump.note.id != note_id);
+        self.index = self.index.min(self.jumps.len());
+    }
+}
+
+impl App {
+    /// Moves the cursor to `position`, reopening the tab of its note through the core
+    /// when it is another note. Returns `false` when the note could not be opened.
+    pub(super) async fn jump_to(&mut self, position: Position) -> bool {
+        let (row, col) = position.cursor;
+        let opened = self.context.notebook.get_opened_note().cloned();
+        if opened.as_ref().map(|note| &note.id) != Some(&position.note.id) {
+            let (note, content) = match self.open_in_core(position.note).await {
+                Some(opened) => opened,
+                None => {
+                    // SelectNote has left the core browsing the tree, so the note being
+                    // edited is opened again to take it back to the editor
+                    if let Some(note) = opened {
+                        self.open_in_core(note).await;
+                    }
+
+                    return false;
+                }
+            };
+
+            let NotebookState { root, .. } = self.glues.state.get_inner().log_unwrap();
+            let note_id = note.id.clone();
+            self.context.notebook.open_note(note, content);
+            self.context.notebook.update_items(root);
+            self.context.notebook.select_item(&note_id);
+        }
+
+        self.context
+            .notebook
+            .get_editor_mut()
+            .move_cursor(CursorMove::Jump(row as u16, col as u16));
+
+        true
+    }
+
+    /// Opens `note` in the core, leaving its tab to the caller. The transition is handled
+    /// there rather than through handle_transition, which calls `jump_to` on jumps.
+    async fn open_in_core(&mut self, note: Note) -> Option<(Note, String)> {
+        let mut transition = None;
+        for event in [NotebookEvent::SelectNote(note), NotebookEvent::OpenNote] {
+            match self.glues.dispatch(event.into()).await {
+                Ok(opened) => transition = Some(opened),
+                Err(error) => {
+                    warn!("{error}");
+                    return None;
+                }
+            }
+        }
+
+        match transition {
+            Some(Transition::Notebook(NotebookTransition::OpenNote { note, content })) => {
+                Some((note, content))
+            }
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use {
+    crate::config,
+    serde::{Deserialize, Serialize},
+    std::{collections::BTreeMap, fs, path::PathBuf},
+};
+
+const FILE_NAME: &str = "registers.toml";
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Register {
+    pub text: String,
+    /// Yanked or deleted as whole lines, so it is pasted below the cursor line.
+    pub linewise: bool,
+}
+
+/// Vim-style registers shared by every tab and kept across sessions.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Registers {
+    /// The register `""` refers to, which is the one written last.
+    unnamed: Option<char>,
+    /// Keyed by the register name, as toml only takes string keys.
+    registers: BTreeMap<String, Register>,
+}
+
+impl Registers {
+    /// Reads `name`, or the unnamed register when none is given.
+    pub fn get(&self, name: Option<char>) -> Option<&Register> {
+        let name = match name {
+            None | Some('"') => self.unnamed?,
+            Some(name) => name.to_ascii_lowercase(),
+        };
+
+        self.registers.get(&name.to_string())
+    }
+
+    pub fn yank(&mut self, name: Option<char>, register: Register) {
+        self.write(name, register, '0');
+    }
+
+    /// Deletes of whole lines shift `"1`-`"9`, smaller ones go to `"-`.
+    pub fn delete(&mut self, name: Option<char>, register: Register) {
+        if !matches!(name, None | Some('"')) {
+            self.write(name, register, '1');
+            return;
+        }
+
+        if !register.linewise && !register.text.contains('\n') {
+            self.write(None, register, '-');
+            return;
+        }
+
+        for n in (1..9).rev() {
+            if let Some(shifted) = self.registers.remove(&n.to_string()) {
+                self.registers.insert((n + 1).to_string(), shifted);
+            }
+        }
+
+        self.write(None, register, '1');
+    }
+
+    fn write(&mut self, name: Option<char>, register: Register, default: char) {
+        let name = match name {
+            Some('_') => return,
+            None | Some('"') => default,
+            Some(name) if name.is_ascii_uppercase() => {
+                let name = name.to_ascii_lowercase();
+                let register = match self.registers.remove(&name.to_string()) {
+                    Some(mut appended) => {
+                        if appended.linewise || register.linewise {
+                            appended.text.push('\n');
+                        }
+
+                        appended.text.push_str(&register.text);
+                        appended.linewise |= register.linewise;
+                        appended
+                    }
+                    None => register,
+                };
+
+                self.registers.insert(name.to_string(), register);
+                self.unnamed = Some(name);
+                return;
+            }
+            Some(name) => name,
+        };
+
+        self.registers.insert(name.to_string(), register);
+        self.unnamed = Some(name);
+    }
+
+    /// Every register with content, the unnamed one first.
+    pub fn list(&self) -> Vec<(char, &Register)> {
+        let unnamed = self.get(None).map(|register| ('"', register));
+        let named = self.registers.iter().filter_map(|(name, register)| {
+            let name = name.chars().next()?;
+
+            Some((name, register))
+        });
+
+        unnamed.into_iter().chain(named).collect()
+    }
+}
+
+pub fn path() -> PathBuf {
+    config::dir().join(FILE_NAME)
+}
+
+pub fn load() -> Registers {
+    let content = match fs::read_to_string(path()) {
+        Ok(content) => content,
+        Err(_) => return Registers::default(),
+    };
+
+    toml::from_str(&content).unwrap_or_else(|error| {
+        warn!("invalid {}: {error}", path().display());
+        Registers::default()
+    })
+}
+
+pub fn save(registers: &Registers) {
+    let result = toml::to_string(registers)
+        .map_err(|error| error.to_string())
+        .and_then(|content| fs::write(path(), content).map_err(|error| error.to_string()));
+    if let Err(error) = result {
+        warn!("failed to save {}: {error}", path().display());
+    }
+}
@@ -0,0 +1,219 @@
+use {
+    crate::{config, context::notebook::TreeItem, logger::*, registers, App},
+    glues_core::{
+        data::Note,
+        state::{GetInner, NotebookState},
+        types::{DirectoryId, NoteId},
+        NotebookEvent,
+    },
+    serde::{Deserialize, Serialize},
+    std::{collections::HashMap, fs, path::PathBuf},
+    tui_textarea::CursorMove,
+};
+
+const FILE_NAME: &str = "sessions.toml";
+
+/// Open tabs and expanded directories of a notebook, keyed by its locator.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+    pub tab_index: Option<usize>,
+    /// Expanded directories, parents first.
+    pub directories: Vec<DirectoryId>,
+    pub tabs: Vec<TabSession>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct TabSession {
+    pub note_id: NoteId,
+    pub directory_id: DirectoryId,
+    pub cursor: (usize, usize),
+    pub scroll: u16,
+}
+
+pub fn path() -> PathBuf {
+    config::dir().join(FILE_NAME)
+}
+
+fn read_all() -> HashMap<String, Session> {
+    let content = match fs::read_to_string(path()) {
+        Ok(content) => content,
+        Err(_) => return HashMap::new(),
+    };
+
+    toml::from_str(&content).unwrap_or_else(|error| {
+        warn!("invalid {}: {error}", path().display());
+        HashMap::new()
+    })
+}
+
+pub fn load(locator: &str) -> Option<Session> {
+    read_all().remove(locator)
+}
+
+pub fn save(locator: &str, session: Session) {
+    let mut sessions = read_all();
+    sessions.insert(locator.to_owned(), session);
+
+    let result = toml::to_string(&sessions)
+        .map_err(|error| error.to_string())
+        .and_then(|content| fs::write(path(), content).map_err(|error| error.to_string()));
+    if let Err(error) = result {
+        warn!("failed to save {}: {error}", path().display());
+    }
+}
+
+impl App {
+    pub(super) fn save_session(&self) {
+        let notebook = &self.context.notebook;
+        registers::save(&notebook.registers);
+
+        let locator = match &notebook.locator {
+            Some(locator) => locator,
+            None => return,
+        };
+
+        let directories = notebook
+            .tree_items
+            .iter()
+            .filter_map(|item| match item {
+                TreeItem::Directory {
+                    value,
+                    opened: true,
+                    ..
+                } => Some(value.id.clone()),
+                _ => None,
+            })
+            .collect();
+        let tabs = notebook
+            .tabs
+            .iter()
+            .map(|tab| TabSession {
+                note_id: tab.note.id.clone(),
+                directory_id: tab.note.directory_id.clone(),
+                cursor: tab.editor.cursor(),
+                // tabs not shown since the restore have yet to scroll
+                scroll: tab.pending_scroll.unwrap_or(tab.viewport.0) as u16,
+            })
+            .collect();
+
+        debug!("Saving session of {locator}");
+        save(
+            locator,
+            Session {
+                tab_index: notebook.tab_index,
+                directories,
+                tabs,
+            },
+        );
+    }
+
+    pub(super) async fn restore_session(&mut self, locator: &str) {
+        let session = match load(locator) {
+            Some(session) => session,
+            None => return,
+        };
+
+        debug!("Restoring session of {locator}");
+        for directory_id in session.directories {
+            self.open_all(directory_id).await;
+        }
+
+        let mut active = None;
+        for (i, tab) in session.tabs.into_iter().enumerate() {
+            self.open_all(tab.directory_id.clone()).await;
+
+            let note = match self.find_note(&tab.directory_id, &tab.note_id) {
+                Some(note) => note,
+                None => continue,
+            };
+            if !self.open_tab(note.clone()).await {
+                continue;
+            }
+
+            let notebook = &mut self.context.notebook;
+            if let Some(editor_tab) = notebook.tab_index.and_then(|i| notebook.tabs.get_mut(i)) {
+                editor_tab.pending_scroll = Some(tab.scroll as usize);
+            }
+
+            let editor = notebook.get_editor_mut();
+            editor.move_cursor(CursorMove::Jump(tab.cursor.0 as u16, tab.cursor.1 as u16));
+
+            if active.is_none() || session.tab_index == Some(i) {
+                active = Some(note);
+            }
+        }
+
+        if let Some(note) = active {
+            self.open_tab(note.clone()).await;
+
+            let NotebookState { root, .. } = self.glues.state.get_inner().log_unwrap();
+            self.context.notebook.update_items(root);
+            self.context.notebook.select_item(&note.id);
+        }
+    }
+
+    /// Opens the directory along with any of its collapsed ancestors.
+    pub(super) async fn open_all(&mut self, directory_id: DirectoryId) {
+        let mut closed = Vec::new();
+        let mut directory_id = directory_id;
+
+        loop {
+            let state: &NotebookState = self.glues.state.get_inner().log_unwrap();
+            if state.check_opened(&directory_id) {
+                break;
+            }
+
+            let db = self.glues.db.as_mut().log_expect("db must exist");
+            let parent_id = match db.fetch_directory(directory_id.clone()).await {
+                Ok(directory) => directory.parent_id,
+                Err(error) => {
+                    warn!("Failed to open directory {directory_id}: {error}");
+                    return;
+                }
+            };
+
+            closed.push(directory_id);
+            directory_id = parent_id;
+        }
+
+        for directory_id in closed.into_iter().rev() {
+            let event = NotebookEvent::OpenDirectory(directory_id).into();
+            match self.glues.dispatch(event).await {
+                Ok(transition) => self.handle_transition(transition).await,
+                Err(error) => {
+                    warn!("{error}");
+                    return;
+                }
+            }
+        }
+    }
+
+    pub(super) fn find_note(&self, directory_id: &DirectoryId, note_id: &NoteId) -> Option<Note> {
+        let state: &NotebookState = self.glues.state.get_inner().ok()?;
+
+        state
+            .root
+            .find(directory_id)?
+            .children
+            .as_ref()?
+            .notes
+            .iter()
+            .find(|note| &note.id == note_id)
+            .cloned()
+    }
+
+    pub(super) async fn open_tab(&mut self, note: Note) -> bool {
+        for event in [NotebookEvent::SelectNote(note), NotebookEvent::OpenNote] {
+            match self.glues.dispatch(event.into()).await {
+                Ok(transition) => self.handle_transition(transition).await,
+                Err(error) => {
+                    warn!("{error}");
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+}
@@ -0,0 +1,266 @@
+use {
+    crate::{
+        config,
+        keymap::{Keymap, Mode},
+        locator,
+        theme::{self, Theme},
+    },
+    serde::Deserialize,
+    std::{collections::HashMap, fs, path::PathBuf, time::SystemTime},
+};
+
+const FILE_NAME: &str = "config.toml";
+
+const DEFAULT: &str = r##"# Glues configuration
+#
+# Changes are applied while Glues is running, no restart needed.
+
+[storage]
+# Notebook to open on startup when no locator is given on the command line.
+# Uses the same syntax as the command line, see `glues --help`.
+# default = "file:~/notes"
+
+[editor]
+# When edited notes are written to the notebook:
+#   "idle"         after `autosave_interval` milliseconds without input
+#   "mode-change"  when leaving insert or visual mode
+#   "tab-switch"   when switching to another tab
+#   "manual"       only with [Ctrl+s] (save note) and [Alt+s] (save all notes)
+# Notes are always saved before closing the notebook or quitting.
+autosave = "idle"
+# Milliseconds of idle time before edited notes are saved.
+autosave_interval = 1500
+# Number of columns a tab character occupies.
+tab_width = 4
+# Number of spaces `>>` and `<<` shift a line by.
+indent_width = 4
+# Show line numbers when a notebook is opened.
+line_numbers = true
+# How the "+ and "* registers reach the system clipboard:
+#   "auto"   wl-copy, xclip, xsel or pbcopy when available, otherwise OSC 52
+#   "osc52"  the OSC 52 escape sequence of the terminal, which can copy but not paste
+#   "none"   keep them inside Glues like any other register
+clipboard = "auto"
+
+[browser]
+# Show the note browser when a notebook is opened.
+show = true
+# Width of the note browser in columns.
+width = 45
+
+[appearance]
+# "dark", "light", or the name of a theme in ~/.glues/themes/<name>.toml.
+# A theme file sets `base = "dark"` or "light" and overrides roles, e.g.
+#   [selection]
+#   fg = "black"
+#   bg = "#87afff"
+# Roles: border, dialog_border, logo, hint, disabled, heading, selection,
+# selection_unfocused, browser_title, tab, tab_active, tab_unfocused, cursor,
+# cursor_line, search, line_number, throbber, log, warning, error, keymap,
+# keymap_separator, statusbar.
+theme = "dark"
+
+# Keys can be remapped per mode: entry, browser, normal, visual, insert and actions.
+# The left side is the key to press, the right side is the default key whose action it triggers.
+# A default key moved elsewhere is unbound, and shortcut hints show the new keys.
+# Examples: "x", "X", "Ctrl+r", "Alt+j", "Esc", "Enter", "Space", "Left".
+[keys.normal]
+# "n" = "j"
+# "e" = "k"
+"##;
+
+#[derive(Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Settings {
+    pub storage: StorageSettings,
+    pub editor: EditorSettings,
+    pub browser: BrowserSettings,
+    pub appearance: AppearanceSettings,
+    pub keys: KeySettings,
+
+    #[serde(skip)]
+    pub keymap: Keymap,
+    #[serde(skip)]
+    pub theme: Theme,
+}
+
+#[derive(Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StorageSettings {
+    pub default: Option<String>,
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EditorSettings {
+    pub autosave: Autosave,
+    pub autosave_interval: u64,
+    pub tab_width: u8,
+    pub indent_width: u8,
+    pub line_numbers: bool,
+    pub clipboard: Clipboard,
+}
+
+impl Default for EditorSettings {
+    fn default() -> Self {
+        Self {
+            autosave: Autosave::Idle,
+            autosave_interval: 1500,
+            tab_width: 4,
+            indent_width: 4,
+            line_numbers: true,
+            clipboard: Clipboard::Auto,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Autosave {
+    Idle,
+    ModeChange,
+    TabSwitch,
+    Manual,
+}
+
+#[derive(Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Clipboard {
+    Auto,
+    Osc52,
+    None,
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BrowserSettings {
+    pub show: bool,
+    pub width: u16,
+}
+
+impl Default for BrowserSettings {
+    fn default() -> Self {
+        Self {
+            show: true,
+            width: 45,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AppearanceSettings {
+    pub theme: String,
+}
+
+impl Default for AppearanceSettings {
+    fn default() -> Self {
+        Self {
+            theme: theme::DARK.to_owned(),
+        }
+    }
+}
+
+#[derive(Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct KeySettings {
+    pub entry: HashMap<String, String>,
+    pub browser: HashMap<String, String>,
+    pub normal: HashMap<String, String>,
+    pub visual: HashMap<String, String>,
+    pub insert: HashMap<String, String>,
+    pub actions: HashMap<String, String>,
+}
+
+impl KeySettings {
+    fn keymap(&self) -> Result<Keymap, String> {
+        let mut keymap = Keymap::default();
+
+        for (mode, name, bindings) in [
+            (Mode::Entry, "entry", &self.entry),
+            (Mode::Browser, "browser", &self.browser),
+            (Mode::Normal, "normal", &self.normal),
+            (Mode::Visual, "visual", &self.visual),
+            (Mode::Insert, "insert", &self.insert),
+            (Mode::Actions, "actions", &self.actions),
+        ] {
+            keymap
+                .bind(mode, bindings)
+                .map_err(|message| format!("keys.{name}: {message}"))?;
+        }
+
+        Ok(keymap)
+    }
+}
+
+pub fn path() -> PathBuf {
+    config::dir().join(FILE_NAME)
+}
+
+/// Latest change to `config.toml` or any user theme, used to reload on edit.
+pub fn modified() -> Option<SystemTime> {
+    let themes = fs::read_dir(theme::dir())
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| Some(entry.ok()?.path()));
+
+    std::iter::once(path())
+        .chain(themes)
+        .filter_map(|path| fs::metadata(path).and_then(|meta| meta.modified()).ok())
+        .max()
+}
+
+/// Reads `config.toml`, writing the documented defaults first if it does not exist yet.
+pub fn load() -> Result<Settings, String> {
+    let path = path();
+    let display = path.display();
+
+    fs::create_dir_all(theme::dir())
+        .map_err(|error| format!("failed to create {}: {error}", theme::dir().display()))?;
+    if !path.exists() {
+        fs::write(&path, DEFAULT).map_err(|error| format!("failed to write {display}: {error}"))?;
+    }
+
+    let content =
+        fs::read_to_string(&path).map_err(|error| format!("failed to read {display}: {error}"))?;
+    let mut settings: Settings =
+        toml::from_str(&content).map_err(|error| format!("invalid {display}: {error}"))?;
+
+    settings
+        .validate()
+        .map_err(|message| format!("invalid {display}: {message}"))?;
+    settings.keymap = settings
+        .keys
+        .keymap()
+        .map_err(|message| format!("invalid {display}: {message}"))?;
+    settings.theme = theme::load(&settings.appearance.theme)?;
+
+    Ok(settings)
+}
+
+impl Settings {
+    fn validate(&self) -> Result<(), String> {
+        if let Some(default) = &self.storage.default {
+            locator::parse(default)
+                .map_err(|message| format!("storage.default '{default}': {message}"))?;
+        }
+
+        if self.editor.autosave_interval < 100 {
+            return Err("editor.autosave_interval must be at least 100 (milliseconds)".to_owned());
+        }
+
+        if !(1..=16).contains(&self.editor.tab_width) {
+            return Err("editor.tab_width must be between 1 and 16".to_owned());
+        }
+
+        if !(1..=16).contains(&self.editor.indent_width) {
+            return Err("editor.indent_width must be between 1 and 16".to_owned());
+        }
+
+        if !(10..=200).contains(&self.browser.width) {
+            return Err("browser.width must be between 10 and 200".to_owned());
+        }
+
+        Ok(())
+    }
+}
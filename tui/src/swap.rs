@@ -0,0 +1,171 @@
+use {
+    crate::{config, logger::*, App},
+    glues_core::types::{DirectoryId, NoteId},
+    serde::{Deserialize, Serialize},
+    std::{
+        fs,
+        path::PathBuf,
+        time::{Duration, Instant},
+    },
+    tui_textarea::{CursorMove, TextArea},
+};
+
+/// Minimum time between two swap writes while keys are being pressed.
+pub const INTERVAL: Duration = Duration::from_secs(1);
+
+/// Unsaved content of a note, kept until it is written to the notebook.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Swap {
+    pub locator: String,
+    pub note_id: NoteId,
+    pub directory_id: DirectoryId,
+    pub note_name: String,
+    pub content: String,
+}
+
+pub fn dir() -> PathBuf {
+    config::dir().join("swap")
+}
+
+fn path(note_id: &NoteId) -> PathBuf {
+    dir().join(format!("{note_id}.toml"))
+}
+
+pub fn write(swap: &Swap) {
+    let path = path(&swap.note_id);
+    let tmp = path.with_extension("tmp");
+
+    // rename is atomic, so a crash while writing never leaves a truncated swap behind
+    let result = fs::create_dir_all(dir())
+        .map_err(|error| error.to_string())
+        .and_then(|_| toml::to_string(swap).map_err(|error| error.to_string()))
+        .and_then(|content| fs::write(&tmp, content).map_err(|error| error.to_string()))
+        .and_then(|_| fs::rename(&tmp, &path).map_err(|error| error.to_string()));
+    if let Err(error) = result {
+        warn!("failed to write swap {}: {error}", path.display());
+    }
+}
+
+pub fn remove(note_id: &NoteId) {
+    let path = path(note_id);
+    if path.exists() {
+        if let Err(error) = fs::remove_file(&path) {
+            warn!("failed to remove swap {}: {error}", path.display());
+        }
+    }
+}
+
+/// Swaps left behind by earlier sessions of the notebook.
+pub fn list(locator: &str) -> Vec<Swap> {
+    fs::read_dir(dir())
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "toml" {
+                return None;
+            }
+
+            let content = fs::read_to_string(&path).ok()?;
+            match toml::from_str::<Swap>(&content) {
+                Ok(swap) => Some(swap),
+                Err(error) => {
+                    warn!("invalid swap {}: {error}", path.display());
+                    None
+                }
+            }
+        })
+        .filter(|swap| swap.locator == locator)
+        .collect()
+}
+
+impl App {
+    /// Writes every buffer that may hold changes not yet saved to the notebook.
+    pub(super) fn write_swaps(&mut self) {
+        let notebook = &self.context.notebook;
+        let locator = match &notebook.locator {
+            Some(locator) => locator,
+            None => return,
+        };
+
+        for tab in notebook.tabs.iter().filter(|tab| tab.dirty) {
+            write(&Swap {
+                locator: locator.clone(),
+                note_id: tab.note.id.clone(),
+                directory_id: tab.note.directory_id.clone(),
+                note_name: tab.note.name.clone(),
+                content: tab.editor.lines().join("\n"),
+            });
+        }
+
+        self.swapped_at = Instant::now();
+    }
+
+    pub(super) fn write_swaps_if_due(&mut self) {
+        if self.swapped_at.elapsed() >= INTERVAL {
+            self.write_swaps();
+        }
+    }
+
+    /// Offers to recover swaps whose content differs from the saved notes.
+    pub(super) async fn check_swaps(&mut self, locator: &str) {
+        let mut swaps = Vec::new();
+
+        for swap in list(locator) {
+            let db = self.glues.db.as_mut().log_expect("db must exist");
+            match db.fetch_note_content(swap.note_id.clone()).await {
+                Ok(content) if content != swap.content => swaps.push(swap),
+                Ok(_) => remove(&swap.note_id),
+                Err(error) => {
+                    warn!("Note '{}' of the swap is gone: {error}", swap.note_name);
+                    remove(&swap.note_id);
+                }
+            }
+        }
+
+        if !swaps.is_empty() {
+            info!("Found {} swap(s) of {locator}", swaps.len());
+            self.context.swaps = Some(swaps);
+        }
+    }
+
+    pub(super) async fn recover_swaps(&mut self) {
+        let swaps = match self.context.swaps.take() {
+            Some(swaps) => swaps,
+            None => return,
+        };
+
+        for swap in swaps {
+            self.open_all(swap.directory_id.clone()).await;
+
+            let note = match self.find_note(&swap.directory_id, &swap.note_id) {
+                Some(note) => note,
+                None => {
+                    warn!("Note '{}' of the swap is not found", swap.note_name);
+                    continue;
+                }
+            };
+            if !self.open_tab(note).await {
+                continue;
+            }
+
+            info!("Recovered note '{}'", swap.note_name);
+            let editor = self.context.notebook.get_editor_mut();
+            let (row, col) = editor.cursor();
+            let mut recovered = TextArea::from(swap.content.lines());
+            recovered.move_cursor(CursorMove::Jump(row as u16, col as u16));
+            *editor = recovered;
+
+            self.context.notebook.mark_dirty();
+            self.context.notebook.apply_yank();
+            self.context.notebook.apply_search();
+        }
+    }
+
+    pub(super) fn discard_swaps(&mut self) {
+        for swap in self.context.swaps.take().unwrap_or_default() {
+            info!("Discarded swap of note '{}'", swap.note_name);
+            remove(&swap.note_id);
+        }
+    }
+}
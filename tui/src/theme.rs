@@ -0,0 +1,229 @@
+use {
+    crate::config,
+    ratatui::style::{Color, Modifier, Style},
+    serde::Deserialize,
+    std::{collections::HashMap, fs, path::PathBuf, str::FromStr},
+};
+
+pub const DARK: &str = "dark";
+pub const LIGHT: &str = "light";
+
+#[derive(Clone)]
+pub struct Theme {
+    pub border: Style,
+    pub dialog_border: Style,
+    pub logo: Style,
+    pub hint: Style,
+    pub disabled: Style,
+    pub heading: Style,
+    pub selection: Style,
+    pub selection_unfocused: Style,
+    pub browser_title: Style,
+    pub tab: Style,
+    pub tab_active: Style,
+    pub tab_unfocused: Style,
+    pub cursor: Style,
+    pub cursor_line: Style,
+    pub search: Style,
+    pub line_number: Style,
+    pub throbber: Style,
+    pub log: Style,
+    pub warning: Style,
+    pub error: Style,
+    pub keymap: Style,
+    pub keymap_separator: Style,
+    pub statusbar: Style,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl Theme {
+    pub fn dark() -> Self {
+        let selection = Style::new().fg(Color::White).bg(Color::Blue);
+        let heading = Style::new().fg(Color::White).bg(Color::DarkGray);
+
+        Self {
+            border: Style::new(),
+            dialog_border: Style::new(),
+            logo: Style::new().fg(Color::DarkGray),
+            hint: Style::new().fg(Color::DarkGray),
+            disabled: Style::new().fg(Color::DarkGray).add_modifier(Modifier::DIM),
+            heading,
+            selection,
+            selection_unfocused: heading,
+            browser_title: Style::new().fg(Color::LightBlue),
+            tab: Style::new().fg(Color::DarkGray),
+            tab_active: selection,
+            tab_unfocused: heading,
+            cursor: selection,
+            cursor_line: Style::new().add_modifier(Modifier::UNDERLINED),
+            search: Style::new().fg(Color::Black).bg(Color::Yellow),
+            line_number: Style::new().fg(Color::DarkGray).add_modifier(Modifier::DIM),
+            throbber: Style::new().fg(Color::Yellow),
+            log: Style::new().fg(Color::Green),
+            warning: Style::new().fg(Color::Yellow),
+            error: Style::new().fg(Color::LightRed),
+            keymap: Style::new().fg(Color::Black).bg(Color::Gray),
+            keymap_separator: Style::new().fg(Color::LightBlue).bg(Color::Gray),
+            statusbar: Style::new().fg(Color::Black).bg(Color::Gray),
+        }
+    }
+
+    pub fn light() -> Self {
+        let selection = Style::new().fg(Color::White).bg(Color::Blue);
+        let heading = Style::new().fg(Color::Black).bg(Color::Gray);
+
+        Self {
+            border: Style::new(),
+            dialog_border: Style::new(),
+            logo: Style::new().fg(Color::Gray),
+            hint: Style::new().fg(Color::DarkGray),
+            disabled: Style::new().fg(Color::Gray),
+            heading,
+            selection,
+            selection_unfocused: heading,
+            browser_title: Style::new().fg(Color::Blue),
+            tab: Style::new().fg(Color::DarkGray),
+            tab_active: selection,
+            tab_unfocused: heading,
+            cursor: selection,
+            cursor_line: Style::new().add_modifier(Modifier::UNDERLINED),
+            search: Style::new().fg(Color::Black).bg(Color::LightYellow),
+            line_number: Style::new().fg(Color::DarkGray),
+            throbber: Style::new().fg(Color::Magenta),
+            log: Style::new().fg(Color::Blue),
+            warning: Style::new().fg(Color::Magenta),
+            error: Style::new().fg(Color::Red),
+            keymap: Style::new().fg(Color::White).bg(Color::DarkGray),
+            keymap_separator: Style::new().fg(Color::LightCyan).bg(Color::DarkGray),
+            statusbar: Style::new().fg(Color::White).bg(Color::DarkGray),
+        }
+    }
+
+    fn role_mut(&mut self, role: &str) -> Option<&mut Style> {
+        let style = match role {
+            "border" => &mut self.border,
+            "dialog_border" => &mut self.dialog_border,
+            "logo" => &mut self.logo,
+            "hint" => &mut self.hint,
+            "disabled" => &mut self.disabled,
+            "heading" => &mut self.heading,
+            "selection" => &mut self.selection,
+            "selection_unfocused" => &mut self.selection_unfocused,
+            "browser_title" => &mut self.browser_title,
+            "tab" => &mut self.tab,
+            "tab_active" => &mut self.tab_active,
+            "tab_unfocused" => &mut self.tab_unfocused,
+            "cursor" => &mut self.cursor,
+            "cursor_line" => &mut self.cursor_line,
+            "search" => &mut self.search,
+            "line_number" => &mut self.line_number,
+            "throbber" => &mut self.throbber,
+            "log" => &mut self.log,
+            "warning" => &mut self.warning,
+            "error" => &mut self.error,
+            "keymap" => &mut self.keymap,
+            "keymap_separator" => &mut self.keymap_separator,
+            "statusbar" => &mut self.statusbar,
+            _ => return None,
+        };
+
+        Some(style)
+    }
+}
+
+/// A user theme at `~/.glues/themes/<name>.toml`.
+/// Each table overrides one role of the `base` preset.
+#[derive(Deserialize)]
+struct ThemeFile {
+    #[serde(default)]
+    base: Option<String>,
+    #[serde(flatten)]
+    roles: HashMap<String, RoleStyle>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RoleStyle {
+    fg: Option<String>,
+    bg: Option<String>,
+    bold: bool,
+    dim: bool,
+    italic: bool,
+    underlined: bool,
+    reversed: bool,
+}
+
+impl RoleStyle {
+    fn to_style(&self) -> Result<Style, String> {
+        let color =
+            |value: &String| Color::from_str(value).map_err(|_| format!("invalid color '{value}'"));
+
+        let mut style = Style::new();
+        if let Some(fg) = &self.fg {
+            style = style.fg(color(fg)?);
+        }
+        if let Some(bg) = &self.bg {
+            style = style.bg(color(bg)?);
+        }
+
+        for (enabled, modifier) in [
+            (self.bold, Modifier::BOLD),
+            (self.dim, Modifier::DIM),
+            (self.italic, Modifier::ITALIC),
+            (self.underlined, Modifier::UNDERLINED),
+            (self.reversed, Modifier::REVERSED),
+        ] {
+            if enabled {
+                style = style.add_modifier(modifier);
+            }
+        }
+
+        Ok(style)
+    }
+}
+
+pub fn dir() -> PathBuf {
+    config::dir().join("themes")
+}
+
+pub fn load(name: &str) -> Result<Theme, String> {
+    match name {
+        DARK => return Ok(Theme::dark()),
+        LIGHT => return Ok(Theme::light()),
+        _ => {}
+    }
+
+    let path = dir().join(format!("{name}.toml"));
+    let display = path.display();
+    let content = fs::read_to_string(&path)
+        .map_err(|error| format!("failed to read theme {display}: {error}"))?;
+    let file: ThemeFile =
+        toml::from_str(&content).map_err(|error| format!("invalid theme {display}: {error}"))?;
+
+    let mut theme = match file.base.as_deref() {
+        None | Some(DARK) => Theme::dark(),
+        Some(LIGHT) => Theme::light(),
+        Some(base) => {
+            return Err(format!(
+                "invalid theme {display}: base must be '{DARK}' or '{LIGHT}', not '{base}'"
+            ))
+        }
+    };
+
+    for (role, style) in file.roles {
+        let target = theme
+            .role_mut(&role)
+            .ok_or_else(|| format!("invalid theme {display}: unknown role '{role}'"))?;
+
+        *target = style
+            .to_style()
+            .map_err(|message| format!("invalid theme {display}: {role}: {message}"))?;
+    }
+
+    Ok(theme)
+}
@@ -1,40 +1,74 @@
+mod motion;
+mod text_object;
+
 use {
     super::{
-        context::{self, notebook::TreeItem, ContextState},
+        config,
+        context::{
+            self,
+            notebook::{BlockInsert, EditorTab, TreeItem, Visual},
+            ContextState, NotebookContext,
+        },
         logger::*,
-        App,
+        settings::Autosave,
+        swap, App,
     },
     glues_core::{
         data::{Directory, Note},
         state::{
-            notebook::{InnerState, VimNormalState},
+            notebook::{
+                CaseKind, CharFind, FindKind, InnerState, Motion, SearchDirection, VimNormalState,
+                VisualKind,
+            },
             GetInner, NotebookState,
         },
         transition::{
-            EntryTransition, NormalModeTransition, NotebookTransition, Transition,
-            VisualModeTransition,
+            EntryTransition, InsertModeTransition, NormalModeTransition, NotebookTransition,
+            Transition, VisualModeTransition,
         },
-        NotebookEvent,
+        types::NoteId,
+        KeyEvent, NotebookEvent,
     },
-    std::time::SystemTime,
+    motion::{motion_range, motion_target},
+    ratatui::crossterm::event::{KeyCode, KeyEvent as CKeyEvent, KeyModifiers},
+    std::{
+        future::Future,
+        ops::{Range, RangeInclusive},
+        pin::Pin,
+        time::SystemTime,
+    },
+    text_object::{text_object_range, ObjectRange},
     tui_textarea::{CursorMove, TextArea},
 };
 
 impl App {
+    /// For transitions which dispatch another event, as an async fn cannot call itself.
+    fn handle_transition_boxed(
+        &mut self,
+        transition: Transition,
+    ) -> Pin<Box<dyn Future<Output = ()> + '_>> {
+        Box::pin(self.handle_transition(transition))
+    }
+
     pub(super) async fn handle_transition(&mut self, transition: Transition) {
         match transition {
             Transition::Entry(transition) => {
                 self.handle_entry_transition(transition).await;
             }
+            Transition::Notebook(NotebookTransition::Batch(transitions)) => {
+                for transition in transitions {
+                    self.handle_notebook_transition(transition).await;
+                }
+            }
             Transition::Notebook(transition) => {
                 self.handle_notebook_transition(transition).await;
             }
             Transition::Log(message) => {
-                log!("{message}");
+                info!("{message}");
                 self.context.last_log = Some((message, SystemTime::now()));
             }
             Transition::Error(message) => {
-                log!("[Err] {message}");
+                error!("{message}");
                 self.context.alert = Some(message);
             }
         }
@@ -43,14 +77,17 @@ impl App {
     pub(super) async fn handle_entry_transition(&mut self, transition: EntryTransition) {
         match transition {
             EntryTransition::OpenNotebook => {
-                log!("Opening notebook");
+                info!("Opening notebook");
 
                 let NotebookState { root, .. } = self.glues.state.get_inner().log_unwrap();
                 self.context.state = ContextState::Notebook;
                 self.context.notebook.update_items(root);
+                self.context.notebook.show_line_number = self.context.settings.editor.line_numbers;
+                self.context.notebook.show_browser = self.context.settings.browser.show;
+                self.context.notebook.clipboard = self.context.settings.editor.clipboard;
             }
             EntryTransition::Inedible(event) => {
-                log!("Inedible event: {event}");
+                debug!("Inedible event: {event}");
             }
             EntryTransition::None => {}
         }
@@ -59,6 +96,21 @@ impl App {
     pub(super) async fn handle_notebook_transition(&mut self, transition: NotebookTransition) {
         use context::notebook::ContextState;
 
+        if let NotebookTransition::CloseNotebook = transition {
+            info!("Closing notebook");
+
+            self.save_session();
+            self.context.state = context::ContextState::Entry;
+            // registers are shared by notebooks and kept until the app quits
+            let registers = std::mem::take(&mut self.context.notebook.registers);
+            self.context.notebook = NotebookContext {
+                registers,
+                ..NotebookContext::default()
+            };
+            self.context.entry.recent = config::recent().await;
+            return;
+        }
+
         let NotebookState {
             root,
             inner_state,
@@ -75,15 +127,51 @@ impl App {
             InnerState::EditingNormalMode(VimNormalState::Idle) => {
                 ContextState::EditorNormalMode { idle: true }
             }
+            InnerState::EditingNormalMode(VimNormalState::Search(_)) => {
+                ContextState::EditorSearchMode
+            }
+            InnerState::EditingNormalMode(VimNormalState::Command) => {
+                ContextState::EditorCommandMode
+            }
             InnerState::EditingNormalMode(_) => ContextState::EditorNormalMode { idle: false },
             InnerState::EditingVisualMode(_) => ContextState::EditorVisualMode,
-            InnerState::EditingInsertMode => ContextState::EditorInsertMode,
+            InnerState::EditingInsertMode | InnerState::EditingReplaceMode => {
+                ContextState::EditorInsertMode
+            }
         };
 
+        let autosave = self.context.settings.editor.autosave;
         if self.context.notebook.state != new_state {
+            // edits are made in insert and visual mode, so only leaving them saves
+            let editing = matches!(
+                self.context.notebook.state,
+                ContextState::EditorInsertMode | ContextState::EditorVisualMode
+            );
+            if autosave == Autosave::ModeChange && editing {
+                self.save_requested = true;
+            }
+
             self.context.notebook.state = new_state;
         }
 
+        let opened_note_id = self
+            .context
+            .notebook
+            .get_opened_note()
+            .map(|note| note.id.clone());
+
+        // leaving a note for another, as `:e`, the browser and tab switches do, is a jump
+        let switches_note = matches!(
+            transition,
+            NotebookTransition::OpenNote { .. }
+                | NotebookTransition::EditingNormalMode(
+                    NormalModeTransition::NextTab(_) | NormalModeTransition::PrevTab(_)
+                )
+        );
+        if switches_note && opened_note_id.is_some() {
+            self.context.notebook.push_jump();
+        }
+
         if &self.context.notebook.tab_index != tab_index {
             self.context.notebook.tab_index = *tab_index;
         }
@@ -93,34 +181,59 @@ impl App {
                 self.context.vim_keymap = Some(kind);
             }
             NotebookTransition::OpenDirectory { id, .. } => {
-                log!("Opening directory {id}");
+                debug!("Opening directory {id}");
                 self.context.notebook.update_items(root);
             }
             NotebookTransition::CloseDirectory(id) => {
-                log!("Closing directory {id}");
+                debug!("Closing directory {id}");
                 self.context.notebook.update_items(root);
                 self.context.notebook.select_item(&id);
             }
             NotebookTransition::OpenNote { note, content } => {
+                let note_id = note.id.clone();
+
                 self.context.notebook.open_note(note, content);
+                self.context.notebook.update_items(root);
+                self.context.notebook.select_item(&note_id);
             }
             NotebookTransition::ViewMode(_note) => {
-                self.context.notebook.mark_dirty();
+                if let Some(insert) = self.context.notebook.block_insert.take() {
+                    if apply_block_insert(self.context.notebook.get_editor_mut(), insert) {
+                        self.context.notebook.mark_dirty();
+                    }
+                }
             }
             NotebookTransition::UpdateNoteContent(note_id) => {
-                self.context.notebook.mark_clean(&note_id);
+                self.note_saved(&note_id);
             }
             NotebookTransition::BrowseNoteTree => {}
             NotebookTransition::RemoveNote {
-                selected_directory, ..
+                ref note,
+                ref selected_directory,
+            } => {
+                self.context.notebook.marks.remove_note(&note.id);
+                self.context.notebook.jumps.remove_note(&note.id);
+                self.context.notebook.select_item(&selected_directory.id);
+                self.context.notebook.update_items(root);
             }
-            | NotebookTransition::RemoveDirectory {
-                selected_directory, ..
+            NotebookTransition::RemoveDirectory {
+                ref selected_directory,
+                ref note_ids,
+                ..
             } => {
+                for note_id in note_ids {
+                    self.context.notebook.marks.remove_note(note_id);
+                    self.context.notebook.jumps.remove_note(note_id);
+                }
                 self.context.notebook.select_item(&selected_directory.id);
                 self.context.notebook.update_items(root);
             }
-            NotebookTransition::RenameNote(_) | NotebookTransition::RenameDirectory(_) => {
+            NotebookTransition::RenameNote(ref note) => {
+                self.context.notebook.marks.rename_note(note);
+                self.context.notebook.jumps.rename_note(note);
+                self.context.notebook.update_items(root);
+            }
+            NotebookTransition::RenameDirectory(_) => {
                 self.context.notebook.update_items(root);
             }
             NotebookTransition::AddNote(Note {
@@ -168,12 +281,27 @@ impl App {
             NotebookTransition::EditingVisualMode(transition) => {
                 self.handle_visual_mode_transition(transition).await;
             }
+            NotebookTransition::EditingInsertMode(transition) => {
+                self.handle_insert_mode_transition(transition);
+            }
             NotebookTransition::Alert(message) => {
-                log!("[Alert] {message}");
+                warn!("{message}");
                 self.context.alert = Some(message);
             }
             _ => {}
         }
+
+        if autosave == Autosave::TabSwitch && opened_note_id.is_some() {
+            let switched = self
+                .context
+                .notebook
+                .get_opened_note()
+                .map(|note| note.id.clone())
+                != opened_note_id;
+            if switched {
+                self.save_requested = true;
+            }
+        }
     }
 
     async fn handle_normal_mode_transition(&mut self, transition: NormalModeTransition) {
@@ -181,18 +309,96 @@ impl App {
 
         match transition {
             IdleMode => {
+                self.context.notebook.register = None;
+                self.context.notebook.visual = None;
                 self.context.notebook.get_editor_mut().cancel_selection();
             }
-            ToggleMode | NumberingMode | GatewayMode | YankMode | DeleteMode | DeleteInsideMode
-            | ChangeMode | ChangeInsideMode => {}
+            ToggleMode | NumberingMode | GatewayMode | YankMode | DeleteMode | ChangeMode
+            | TextObjectMode | IndentMode | OutdentMode | CaseMode | ReplaceCharMode
+            | RegisterMode | FindMode | RecordMacroMode | PlayMacroMode | MarkMode
+            | JumpToMarkMode => {}
+            ReplaceMode => {
+                self.context.notebook.overwritten.clear();
+            }
+            StartRecording(register) => {
+                self.context.last_log = Some((format!("Recording @{register}"), SystemTime::now()));
+            }
+            StopRecording(register) => {
+                self.context.last_log = Some((format!("Recorded @{register}"), SystemTime::now()));
+            }
+            SetMark(name) => {
+                let position = self.context.notebook.position();
+                self.context.notebook.marks.set(name, position);
+            }
+            JumpToMark(name, linewise) => {
+                let note = self
+                    .context
+                    .notebook
+                    .get_opened_note()
+                    .log_expect("no opened note");
+                let position = match self.context.notebook.marks.get(name, note) {
+                    Some(position) => position,
+                    None => {
+                        let message = format!("Mark not set: {name}");
+                        self.context.last_log = Some((message, SystemTime::now()));
+                        return;
+                    }
+                };
+
+                self.context.notebook.push_jump();
+                if self.jump_to(position).await && linewise {
+                    move_cursor_to_line_non_empty_start(self.context.notebook.get_editor_mut());
+                }
+            }
+            JumpBack(n) => {
+                let current = self.context.notebook.position();
+                if let Some(position) = self.context.notebook.jumps.back(current, n) {
+                    self.jump_to(position).await;
+                }
+            }
+            JumpForward(n) => {
+                if let Some(position) = self.context.notebook.jumps.forward(n) {
+                    self.jump_to(position).await;
+                }
+            }
+            SelectRegister(name) => {
+                self.context.notebook.register = Some(name);
+            }
+            ShowRegisters => {
+                self.context.register_viewer = true;
+            }
             NextTab(note_id) | PrevTab(note_id) => {
                 let NotebookState { root, .. } = self.glues.state.get_inner().log_unwrap();
 
                 self.context.notebook.update_items(root);
                 self.context.notebook.select_item(&note_id);
                 self.context.notebook.apply_yank();
+                self.context.notebook.apply_search();
+            }
+            QuitTab { force } => {
+                let notebook = &mut self.context.notebook;
+                let tab = notebook.tab_index.and_then(|i| notebook.tabs.get(i));
+                let tab = tab.log_expect("opened tab must exist");
+                if tab.dirty && !force {
+                    let message = "No write since last change (add ! to override)".to_owned();
+                    warn!("{message}");
+                    self.context.alert = Some(message);
+                    return;
+                }
+
+                // `:q!` drops the changes, which closing the tab would otherwise save
+                let note_id = tab.note.id.clone();
+                self.note_saved(&note_id);
+
+                let transition = self
+                    .glues
+                    .dispatch(NotebookEvent::CloseTab.into())
+                    .await
+                    .log_unwrap();
+                self.handle_transition_boxed(transition).await;
             }
             CloseTab(note_id) => {
+                self.save_tabs(Some(note_id.clone())).await;
                 self.context.notebook.close_tab(&note_id);
 
                 let state: &NotebookState = self.glues.state.get_inner().log_unwrap();
@@ -201,8 +407,81 @@ impl App {
                 let note_id = &state.get_selected_note().log_unwrap().id;
                 self.context.notebook.update_items(&state.root);
                 self.context.notebook.select_item(note_id);
-                self.context.notebook.apply_yank();
+                if self.context.notebook.tab_index.is_some() {
+                    self.context.notebook.apply_yank();
+                    self.context.notebook.apply_search();
+                }
+            }
+            SearchMode(direction) => {
+                let prefix = match direction {
+                    SearchDirection::Forward => '/',
+                    SearchDirection::Backward => '?',
+                };
+
+                self.context.notebook.open_command_line(prefix);
+            }
+            ConfirmSearch(direction, pattern) => {
+                if !self.context.notebook.confirm_search(direction, pattern) {
+                    self.search_not_found();
+                }
+            }
+            CancelSearch => {
+                self.context.notebook.cancel_search();
+            }
+            SearchNext(n) => {
+                self.context.notebook.push_jump();
+                if !self.context.notebook.search_next(n, false) {
+                    self.search_not_found();
+                }
+            }
+            SearchPrev(n) => {
+                self.context.notebook.push_jump();
+                if !self.context.notebook.search_next(n, true) {
+                    self.search_not_found();
+                }
+            }
+            ClearSearch => {
+                self.context.notebook.clear_search();
+            }
+            CommandMode => {
+                self.context.notebook.open_command_line(':');
+            }
+            CloseCommand => {
+                self.context.notebook.command_line = None;
+            }
+            SaveNote => {
+                self.save_current().await;
+                self.context.last_log = Some(("Note saved".to_owned(), SystemTime::now()));
+            }
+            SaveAllNotes => {
+                self.save().await;
+                self.context.last_log = Some(("All notes saved".to_owned(), SystemTime::now()));
+            }
+            MoveCursorToAddress(address) => {
+                self.context.notebook.push_jump();
+                let editor = self.context.notebook.get_editor_mut();
+                let last = editor.lines().len() - 1;
+                let row = address.resolve(editor.cursor().0, last);
+
+                editor.move_cursor(CursorMove::Jump(row as u16, 0));
+                move_cursor_to_line_non_empty_start(editor);
             }
+            Substitute(substitution) => match self.context.notebook.substitute(&substitution) {
+                Ok((0, _)) => {
+                    let message = format!("Pattern not found: {}", substitution.pattern);
+                    self.context.last_log = Some((message, SystemTime::now()));
+                }
+                Ok((count, lines)) => {
+                    move_cursor_to_line_non_empty_start(self.context.notebook.get_editor_mut());
+
+                    let message = format!("{count} substitution(s) on {lines} line(s)");
+                    self.context.last_log = Some((message, SystemTime::now()));
+                }
+                Err(message) => {
+                    warn!("{message}");
+                    self.context.alert = Some(message);
+                }
+            },
             ToggleLineNumbers => {
                 self.context.notebook.show_line_number = !self.context.notebook.show_line_number;
             }
@@ -267,32 +546,45 @@ impl App {
                 move_cursor_to_line_non_empty_start(self.context.notebook.get_editor_mut());
             }
             MoveCursorTop => {
+                self.context.notebook.push_jump();
                 self.context
                     .notebook
                     .get_editor_mut()
                     .move_cursor(CursorMove::Top);
             }
             MoveCursorBottom => {
+                self.context.notebook.push_jump();
                 self.context
                     .notebook
                     .get_editor_mut()
                     .move_cursor(CursorMove::Bottom);
             }
             MoveCursorToLine(n) => {
+                self.context.notebook.push_jump();
                 let editor = self.context.notebook.get_editor_mut();
                 editor.move_cursor(CursorMove::Jump((n - 1) as u16, 0));
                 editor.move_cursor(CursorMove::WordForward);
             }
+            MoveCursor(motion, n) => {
+                self.context.notebook.push_jump();
+                self.move_cursor_by(motion, n);
+            }
+            ScrollHalfPageDown(n) => self.scroll_pages(n, true, true),
+            ScrollHalfPageUp(n) => self.scroll_pages(n, true, false),
+            ScrollPageDown(n) => self.scroll_pages(n, false, true),
+            ScrollPageUp(n) => self.scroll_pages(n, false, false),
             InsertNewLineBelow => {
                 let editor = self.context.notebook.get_editor_mut();
                 editor.move_cursor(CursorMove::End);
                 editor.insert_newline();
+                self.context.notebook.mark_dirty();
             }
             InsertNewLineAbove => {
                 let editor = self.context.notebook.get_editor_mut();
                 editor.move_cursor(CursorMove::Head);
                 editor.insert_newline();
                 editor.move_cursor(CursorMove::Up);
+                self.context.notebook.mark_dirty();
             }
             InsertAtCursor => {}
             InsertAtLineStart => {
@@ -321,12 +613,16 @@ impl App {
                 editor.move_cursor(cursor_move);
                 editor.cut();
                 self.context.notebook.mark_dirty();
-                self.context.notebook.update_yank();
+                self.context.notebook.store_delete(false);
             }
             Paste => {
-                let line_yanked = self.context.notebook.line_yanked;
+                let linewise = match self.context.notebook.load_register() {
+                    Some(linewise) => linewise,
+                    None => return,
+                };
+
                 let editor = self.context.notebook.get_editor_mut();
-                if line_yanked {
+                if linewise {
                     editor.move_cursor(CursorMove::End);
                     editor.insert_newline();
                     editor.paste();
@@ -336,6 +632,7 @@ impl App {
                 }
 
                 self.context.notebook.mark_dirty();
+                self.context.notebook.apply_yank();
             }
             Undo => {
                 self.context.notebook.get_editor_mut().undo();
@@ -356,66 +653,88 @@ impl App {
                 editor.copy();
                 editor.cancel_selection();
                 editor.move_cursor(CursorMove::Jump(cursor.0 as u16, cursor.1 as u16));
-                self.context.notebook.line_yanked = true;
-                self.context.notebook.update_yank();
+                self.context.notebook.store_yank(true);
             }
             DeleteLines(n) => {
+                delete_lines(self.context.notebook.get_editor_mut(), n);
+                self.context.notebook.mark_dirty();
+                self.context.notebook.store_delete(true);
+            }
+            DeleteLinesAndInsert(n) => {
+                cut_lines(self.context.notebook.get_editor_mut(), n);
+                self.context.notebook.mark_dirty();
+                self.context.notebook.store_delete(true);
+            }
+            DeleteTextObject(..) | ChangeTextObject(..) | DeleteMotion(..) | ChangeMotion(..) => {
+                let change = matches!(transition, ChangeTextObject(..) | ChangeMotion(..));
+                let screen = self.context.notebook.screen_rows();
                 let editor = self.context.notebook.get_editor_mut();
-                let (row, _) = editor.cursor();
-
-                editor.move_cursor(CursorMove::Head);
-                editor.start_selection();
-                let cursor_move = cursor_move_down(editor, n - 1);
-                editor.move_cursor(cursor_move);
-                editor.move_cursor(CursorMove::End);
-                editor.cut();
+                let range = match transition {
+                    DeleteTextObject(object, n) | ChangeTextObject(object, n) => {
+                        text_object_range(editor, object, n)
+                    }
+                    DeleteMotion(motion, n) | ChangeMotion(motion, n) => {
+                        motion_range(editor, motion, n, screen)
+                    }
+                    _ => None,
+                };
+                let range = match range {
+                    Some(range) => range,
+                    None => return,
+                };
 
-                if row == 0 {
-                    editor.move_cursor(CursorMove::Down);
-                    editor.move_cursor(CursorMove::Head);
-                    editor.delete_char();
+                let ObjectRange {
+                    begin,
+                    end,
+                    linewise,
+                } = range;
+                editor.move_cursor(CursorMove::Jump(begin.0 as u16, begin.1 as u16));
+                if linewise && change {
+                    cut_lines(editor, end.0 - begin.0 + 1);
+                } else if linewise {
+                    delete_lines(editor, end.0 - begin.0 + 1);
+                } else if begin == end {
+                    return;
                 } else {
-                    editor.delete_char();
-                    editor.move_cursor(CursorMove::Down);
+                    editor.start_selection();
+                    editor.move_cursor(CursorMove::Jump(end.0 as u16, end.1 as u16));
+                    editor.cut();
                 }
 
-                move_cursor_to_line_non_empty_start(editor);
-                self.context.notebook.line_yanked = true;
-                self.context.notebook.mark_dirty();
-                self.context.notebook.update_yank();
-            }
-            DeleteLinesAndInsert(n) => {
-                let editor = self.context.notebook.get_editor_mut();
-                editor.move_cursor(CursorMove::Head);
-                editor.start_selection();
-                let cursor_move = cursor_move_down(editor, n - 1);
-                editor.move_cursor(cursor_move);
-                editor.move_cursor(CursorMove::End);
-                editor.cut();
-                self.context.notebook.line_yanked = true;
                 self.context.notebook.mark_dirty();
-                self.context.notebook.update_yank();
+                self.context.notebook.store_delete(linewise);
             }
-            DeleteInsideWord(n) => {
+            YankTextObject(..) | YankMotion(..) => {
+                let screen = self.context.notebook.screen_rows();
                 let editor = self.context.notebook.get_editor_mut();
-                let cursor = editor.cursor();
-                editor.move_cursor(CursorMove::WordBack);
-                editor.move_cursor(CursorMove::WordEnd);
-                let cursor_be = editor.cursor();
-                editor.move_cursor(CursorMove::Jump(cursor.0 as u16, cursor.1 as u16));
+                let range = match transition {
+                    YankTextObject(object, n) => text_object_range(editor, object, n),
+                    YankMotion(motion, n) => motion_range(editor, motion, n, screen),
+                    _ => None,
+                };
+                let range = match range {
+                    Some(range) => range,
+                    None => return,
+                };
 
-                if cursor_be >= cursor {
-                    editor.move_cursor(CursorMove::WordBack);
+                let ObjectRange {
+                    begin,
+                    end,
+                    linewise,
+                } = range;
+                if linewise {
+                    select_lines(editor, begin.0, end.0);
+                } else if begin == end {
+                    return;
+                } else {
+                    editor.move_cursor(CursorMove::Jump(begin.0 as u16, begin.1 as u16));
+                    editor.start_selection();
+                    editor.move_cursor(CursorMove::Jump(end.0 as u16, end.1 as u16));
                 }
 
-                editor.start_selection();
-                move_cursor_word_end(editor, n);
-                editor.move_cursor(CursorMove::Forward);
-                editor.cut();
-
-                self.context.notebook.line_yanked = false;
-                self.context.notebook.mark_dirty();
-                self.context.notebook.update_yank();
+                editor.copy();
+                editor.move_cursor(CursorMove::Jump(begin.0 as u16, begin.1 as u16));
+                self.context.notebook.store_yank(linewise);
             }
             DeleteWordEnd(n) => {
                 let editor = self.context.notebook.get_editor_mut();
@@ -424,9 +743,8 @@ impl App {
                 editor.move_cursor(CursorMove::Forward);
                 editor.cut();
 
-                self.context.notebook.line_yanked = false;
                 self.context.notebook.mark_dirty();
-                self.context.notebook.update_yank();
+                self.context.notebook.store_delete(false);
             }
             DeleteWordBack(n) => {
                 let editor = self.context.notebook.get_editor_mut();
@@ -434,9 +752,8 @@ impl App {
                 move_cursor_word_back(editor, n);
                 editor.cut();
 
-                self.context.notebook.line_yanked = false;
                 self.context.notebook.mark_dirty();
-                self.context.notebook.update_yank();
+                self.context.notebook.store_delete(false);
             }
             DeleteLineStart => {
                 let editor = self.context.notebook.get_editor_mut();
@@ -444,9 +761,8 @@ impl App {
                 editor.move_cursor(CursorMove::Head);
                 editor.cut();
 
-                self.context.notebook.line_yanked = false;
                 self.context.notebook.mark_dirty();
-                self.context.notebook.update_yank();
+                self.context.notebook.store_delete(false);
             }
             DeleteLineEnd(n) => {
                 let editor = self.context.notebook.get_editor_mut();
@@ -456,9 +772,109 @@ impl App {
                 editor.move_cursor(CursorMove::End);
                 editor.cut();
 
-                self.context.notebook.line_yanked = false;
                 self.context.notebook.mark_dirty();
-                self.context.notebook.update_yank();
+                self.context.notebook.store_delete(false);
+            }
+            IndentLines(n) | OutdentLines(n) => {
+                let width = self.context.settings.editor.indent_width as usize;
+                let editor = self.context.notebook.get_editor_mut();
+                let (row, _) = editor.cursor();
+                let bottom = row.saturating_add(n - 1).min(editor.lines().len() - 1);
+
+                let changed = match transition {
+                    IndentLines(_) => indent_lines(editor, row..=bottom, width),
+                    _ => outdent_lines(editor, row..=bottom, width),
+                };
+                if changed {
+                    self.context.notebook.mark_dirty();
+                }
+            }
+            JoinLines(n) => {
+                if join_lines(self.context.notebook.get_editor_mut(), n) {
+                    self.context.notebook.mark_dirty();
+                }
+            }
+            CaseChars(kind, n) => {
+                let editor = self.context.notebook.get_editor_mut();
+                let (row, col) = editor.cursor();
+                let len = editor.lines()[row].chars().count();
+                if col >= len {
+                    return;
+                }
+
+                let end = col.saturating_add(n).min(len);
+                let changed = change_case(editor, (row, col), (row, end), kind);
+                editor.move_cursor(CursorMove::Jump(row as u16, end as u16));
+                if end == len {
+                    editor.move_cursor(CursorMove::Back);
+                }
+
+                if changed {
+                    self.context.notebook.mark_dirty();
+                }
+            }
+            CaseLines(kind, _)
+            | CaseWordEnd(kind, _)
+            | CaseWordBack(kind, _)
+            | CaseLineStart(kind)
+            | CaseLineEnd(kind, _)
+            | CaseToChar(kind, ..)
+            | CaseTextObject(kind, ..)
+            | CaseMotion(kind, ..) => {
+                let screen = self.context.notebook.screen_rows();
+                let editor = self.context.notebook.get_editor_mut();
+                let cursor = editor.cursor();
+                let (begin, end) = match case_range(editor, &transition, screen) {
+                    Some(range) => range,
+                    None => return,
+                };
+
+                let changed = change_case(editor, begin, end, kind);
+                // `guu` and the like keep the cursor where it was
+                if matches!(transition, CaseLines(..)) {
+                    editor.move_cursor(CursorMove::Jump(cursor.0 as u16, cursor.1 as u16));
+                }
+
+                if changed {
+                    self.context.notebook.mark_dirty();
+                }
+            }
+            ReplaceChars(c, n) => {
+                let editor = self.context.notebook.get_editor_mut();
+                let (row, col) = editor.cursor();
+                if col.saturating_add(n) > editor.lines()[row].chars().count() {
+                    return;
+                }
+
+                replace_range(editor, (row, col), (row, col + n), &c.to_string().repeat(n));
+                editor.move_cursor(CursorMove::Jump(row as u16, (col + n - 1) as u16));
+                self.context.notebook.mark_dirty();
+            }
+            MoveCursorToChar(find, n, repeat) => {
+                let editor = self.context.notebook.get_editor_mut();
+                move_cursor_to_char(editor, find, n, repeat);
+            }
+            DeleteToChar(find, n) => {
+                let editor = self.context.notebook.get_editor_mut();
+                if !select_to_char(editor, find, n) {
+                    return;
+                }
+
+                editor.cut();
+                self.context.notebook.mark_dirty();
+                self.context.notebook.store_delete(false);
+            }
+            YankToChar(find, n) => {
+                let editor = self.context.notebook.get_editor_mut();
+                if !select_to_char(editor, find, n) {
+                    return;
+                }
+
+                let (begin, _) = editor.selection_range().log_expect("selection not found");
+                editor.copy();
+                editor.cancel_selection();
+                editor.move_cursor(CursorMove::Jump(begin.0 as u16, begin.1 as u16));
+                self.context.notebook.store_yank(false);
             }
         };
     }
@@ -468,9 +884,82 @@ impl App {
 
         match transition {
             IdleMode => {
+                self.start_visual(VisualKind::Charwise);
                 self.context.notebook.get_editor_mut().start_selection();
             }
-            NumberingMode | GatewayMode => {}
+            LinewiseMode => {
+                self.start_visual(VisualKind::Linewise);
+            }
+            BlockwiseMode => {
+                self.start_visual(VisualKind::Blockwise);
+            }
+            ChangeKind(kind) => {
+                let notebook = &mut self.context.notebook;
+                let visual = notebook
+                    .visual
+                    .as_mut()
+                    .log_expect("visual mode not started");
+                visual.kind = kind;
+                let anchor = visual.anchor;
+
+                let editor = notebook.get_editor_mut();
+                editor.cancel_selection();
+                if kind == VisualKind::Charwise {
+                    let cursor = editor.cursor();
+                    editor.move_cursor(CursorMove::Jump(anchor.0 as u16, anchor.1 as u16));
+                    editor.start_selection();
+                    editor.move_cursor(CursorMove::Jump(cursor.0 as u16, cursor.1 as u16));
+                }
+            }
+            NumberingMode | GatewayMode | RegisterMode | FindMode | TextObjectMode => {}
+            SelectTextObject(object, kind, n) => {
+                let notebook = &mut self.context.notebook;
+                let ObjectRange { begin, end, .. } =
+                    match text_object_range(notebook.get_editor(), object, n) {
+                        Some(range) if range.begin != range.end => range,
+                        _ => return,
+                    };
+
+                notebook.visual = Some(Visual {
+                    kind,
+                    anchor: begin,
+                });
+
+                let editor = notebook.get_editor_mut();
+                editor.cancel_selection();
+                if kind == VisualKind::Linewise {
+                    editor.move_cursor(CursorMove::Jump(end.0 as u16, 0));
+                } else {
+                    editor.move_cursor(CursorMove::Jump(begin.0 as u16, begin.1 as u16));
+                    editor.start_selection();
+                    // the selection of visual mode takes in the character under the cursor
+                    editor.move_cursor(CursorMove::Jump(end.0 as u16, end.1 as u16));
+                    editor.move_cursor(CursorMove::Back);
+                }
+            }
+            MoveCursorToChar(find, n, repeat) => {
+                let editor = self.context.notebook.get_editor_mut();
+                move_cursor_to_char(editor, find, n, repeat);
+            }
+            IndentSelection(n) | OutdentSelection(n) => {
+                let width = (self.context.settings.editor.indent_width as usize).saturating_mul(n);
+                let visual = self.context.notebook.visual.take();
+                let visual = visual.log_expect("visual mode not started");
+                let editor = self.context.notebook.get_editor_mut();
+                let ((top, _), (bottom, _)) = visual.block(editor.cursor());
+                editor.cancel_selection();
+
+                let changed = match transition {
+                    IndentSelection(_) => indent_lines(editor, top..=bottom, width),
+                    _ => outdent_lines(editor, top..=bottom, width),
+                };
+                if changed {
+                    self.context.notebook.mark_dirty();
+                }
+            }
+            SelectRegister(name) => {
+                self.context.notebook.register = Some(name);
+            }
             MoveCursorDown(n) => {
                 let editor = self.context.notebook.get_editor_mut();
                 let cursor_move = cursor_move_down(editor, n);
@@ -553,37 +1042,200 @@ impl App {
                 editor.move_cursor(CursorMove::Jump((n - 1) as u16, 0));
                 editor.move_cursor(CursorMove::WordForward);
             }
+            MoveCursor(motion, n) => self.move_cursor_by(motion, n),
+            ScrollHalfPageDown(n) => self.scroll_pages(n, true, true),
+            ScrollHalfPageUp(n) => self.scroll_pages(n, true, false),
+            ScrollPageDown(n) => self.scroll_pages(n, false, true),
+            ScrollPageUp(n) => self.scroll_pages(n, false, false),
             YankSelection => {
+                let visual = self.context.notebook.visual.take();
                 let editor = self.context.notebook.get_editor_mut();
-                reselect_for_yank(editor);
-                editor.copy();
-                self.context.notebook.line_yanked = false;
-                self.context.notebook.update_yank();
+                let cursor = editor.cursor();
+
+                let linewise = match visual {
+                    Some(visual) if visual.kind == VisualKind::Linewise => {
+                        let ((top, _), (bottom, _)) = visual.block(cursor);
+                        select_lines(editor, top, bottom);
+                        editor.copy();
+
+                        let (row, col) = visual.anchor.min(cursor);
+                        editor.move_cursor(CursorMove::Jump(row as u16, col as u16));
+                        true
+                    }
+                    Some(visual) if visual.kind == VisualKind::Blockwise => {
+                        let (begin, end) = visual.block(cursor);
+                        editor.set_yank_text(block_text(editor, begin, end));
+                        editor.move_cursor(CursorMove::Jump(begin.0 as u16, begin.1 as u16));
+                        false
+                    }
+                    _ => {
+                        reselect_for_yank(editor);
+                        editor.copy();
+                        false
+                    }
+                };
+
+                self.context.notebook.store_yank(linewise);
             }
             DeleteSelection => {
+                let visual = self.context.notebook.visual.take();
                 let editor = self.context.notebook.get_editor_mut();
-                reselect_for_yank(editor);
-                editor.cut();
-                self.context.notebook.line_yanked = false;
+                let cursor = editor.cursor();
+
+                let linewise = match visual {
+                    Some(visual) if visual.kind == VisualKind::Linewise => {
+                        let ((top, _), (bottom, _)) = visual.block(cursor);
+                        editor.move_cursor(CursorMove::Jump(top as u16, 0));
+                        delete_lines(editor, bottom - top + 1);
+                        true
+                    }
+                    Some(visual) if visual.kind == VisualKind::Blockwise => {
+                        let (begin, end) = visual.block(cursor);
+                        delete_block(editor, begin, end);
+                        false
+                    }
+                    _ => {
+                        reselect_for_yank(editor);
+                        editor.cut();
+                        false
+                    }
+                };
+
                 self.context.notebook.mark_dirty();
-                self.context.notebook.update_yank();
+                self.context.notebook.store_delete(linewise);
             }
             DeleteSelectionAndInsertMode => {
+                let visual = self.context.notebook.visual.take();
                 let editor = self.context.notebook.get_editor_mut();
-                reselect_for_yank(editor);
-                editor.cut();
-                self.context.notebook.line_yanked = false;
+                let cursor = editor.cursor();
+
+                let linewise = match visual {
+                    Some(visual) if visual.kind == VisualKind::Linewise => {
+                        let ((top, _), (bottom, _)) = visual.block(cursor);
+                        editor.move_cursor(CursorMove::Jump(top as u16, 0));
+                        cut_lines(editor, bottom - top + 1);
+                        true
+                    }
+                    Some(visual) if visual.kind == VisualKind::Blockwise => {
+                        let (begin, end) = visual.block(cursor);
+                        delete_block(editor, begin, end);
+                        let insert = start_block_insert(editor, begin.0..=end.0, begin.1, false);
+                        self.context.notebook.block_insert = Some(insert);
+                        false
+                    }
+                    _ => {
+                        reselect_for_yank(editor);
+                        editor.cut();
+                        false
+                    }
+                };
+
                 self.context.notebook.mark_dirty();
-                self.context.notebook.update_yank();
+                self.context.notebook.store_delete(linewise);
+            }
+            InsertBlock | AppendBlock => {
+                let visual = self.context.notebook.visual.take();
+                let visual = visual.log_expect("visual mode not started");
+                let editor = self.context.notebook.get_editor_mut();
+                let ((top, left), (bottom, right)) = visual.block(editor.cursor());
+
+                let insert = match transition {
+                    InsertBlock => start_block_insert(editor, top..=bottom, left, false),
+                    _ => start_block_insert(editor, top..=bottom, right + 1, true),
+                };
+                self.context.notebook.block_insert = Some(insert);
             }
         }
     }
 
+    fn move_cursor_by(&mut self, motion: Motion, n: usize) {
+        let screen = self.context.notebook.screen_rows();
+        let editor = self.context.notebook.get_editor_mut();
+        if let Some((row, col)) = motion_target(editor, motion, n, screen) {
+            editor.move_cursor(CursorMove::Jump(row as u16, col as u16));
+        }
+    }
+
+    /// Scrolls `n` pages of half the view, or of all but two of its rows when not `half`.
+    fn scroll_pages(&mut self, n: usize, half: bool, down: bool) {
+        let height = self.context.notebook.editor_height;
+        let page = match half {
+            true => height / 2,
+            false => height.saturating_sub(2),
+        };
+        let rows = page.max(1).saturating_mul(n).min(isize::MAX as usize) as isize;
+        let rows = if down { rows } else { -rows };
+
+        let tab = self.context.notebook.get_tab_mut();
+        scroll_view(tab, rows, height);
+    }
+
+    fn start_visual(&mut self, kind: VisualKind) {
+        let anchor = self.context.notebook.get_editor().cursor();
+        self.context.notebook.visual = Some(Visual { kind, anchor });
+    }
+
+    fn handle_insert_mode_transition(&mut self, transition: InsertModeTransition) {
+        match transition {
+            InsertModeTransition::Key(key) => {
+                let notebook = &mut self.context.notebook;
+                let yank = notebook.get_editor().yank_text();
+                if notebook.get_editor_mut().input(to_input(key)) {
+                    notebook.mark_dirty();
+                }
+                // Ctrl+w, Ctrl+k and the like delete into the yank buffer
+                if notebook.get_editor().yank_text() != yank {
+                    notebook.store_delete(false);
+                }
+            }
+            InsertModeTransition::Overwrite(key) => {
+                let notebook = &mut self.context.notebook;
+                let mut overwritten = std::mem::take(&mut notebook.overwritten);
+                if overwrite(notebook.get_editor_mut(), &mut overwritten, key) {
+                    notebook.mark_dirty();
+                }
+
+                notebook.overwritten = overwritten;
+            }
+        }
+    }
+
+    fn search_not_found(&mut self) {
+        let pattern = self
+            .context
+            .notebook
+            .search
+            .as_ref()
+            .map(|search| search.pattern.clone())
+            .unwrap_or_default();
+
+        self.context.last_log = Some((format!("Pattern not found: {pattern}"), SystemTime::now()));
+    }
+
     pub(crate) async fn save(&mut self) {
-        let mut transitions = vec![];
+        self.save_tabs(None).await;
+    }
+
+    pub(crate) async fn save_current(&mut self) {
+        let note_id = match self.context.notebook.get_opened_note() {
+            Some(note) => note.id.clone(),
+            None => return,
+        };
+
+        self.save_tabs(Some(note_id)).await;
+    }
+
+    fn note_saved(&mut self, note_id: &NoteId) {
+        self.context.notebook.mark_clean(note_id);
+        swap::remove(note_id);
+    }
+
+    /// Saves the dirty tabs, or only the given note when one is passed.
+    async fn save_tabs(&mut self, only: Option<NoteId>) {
+        let mut saved = vec![];
 
         for tab in self.context.notebook.tabs.iter() {
-            if !tab.dirty {
+            if !tab.dirty || only.as_ref().is_some_and(|note_id| note_id != &tab.note.id) {
                 continue;
             }
 
@@ -593,19 +1245,47 @@ impl App {
             }
             .into();
 
+            // handled here rather than through handle_transition, which calls this on CloseTab
             let transition = self.glues.dispatch(event).await.log_unwrap();
-            transitions.push(transition);
+            if let Transition::Notebook(NotebookTransition::UpdateNoteContent(note_id)) = transition
+            {
+                saved.push(note_id);
+            }
         }
 
-        for transition in transitions {
-            self.handle_transition(transition).await;
+        for note_id in saved {
+            self.note_saved(&note_id);
         }
     }
 }
 
+/// Turns a key the core replays or passed through back into editor input.
+fn to_input(key: KeyEvent) -> CKeyEvent {
+    let (code, modifiers) = match key {
+        KeyEvent::Char(c) => (KeyCode::Char(c), KeyModifiers::NONE),
+        KeyEvent::Ctrl(c) => (KeyCode::Char(c), KeyModifiers::CONTROL),
+        KeyEvent::Alt(c) => (KeyCode::Char(c), KeyModifiers::ALT),
+        KeyEvent::Left => (KeyCode::Left, KeyModifiers::NONE),
+        KeyEvent::Right => (KeyCode::Right, KeyModifiers::NONE),
+        KeyEvent::Up => (KeyCode::Up, KeyModifiers::NONE),
+        KeyEvent::Down => (KeyCode::Down, KeyModifiers::NONE),
+        KeyEvent::Home => (KeyCode::Home, KeyModifiers::NONE),
+        KeyEvent::End => (KeyCode::End, KeyModifiers::NONE),
+        KeyEvent::PageUp => (KeyCode::PageUp, KeyModifiers::NONE),
+        KeyEvent::PageDown => (KeyCode::PageDown, KeyModifiers::NONE),
+        KeyEvent::Tab => (KeyCode::Tab, KeyModifiers::NONE),
+        KeyEvent::Backspace => (KeyCode::Backspace, KeyModifiers::NONE),
+        KeyEvent::Delete => (KeyCode::Delete, KeyModifiers::NONE),
+        KeyEvent::Enter => (KeyCode::Enter, KeyModifiers::NONE),
+        KeyEvent::Esc => (KeyCode::Esc, KeyModifiers::NONE),
+    };
+
+    CKeyEvent::new(code, modifiers)
+}
+
 fn cursor_move_forward(editor: &TextArea, n: usize) -> CursorMove {
     let (row, col) = editor.cursor();
-    if col + n >= editor.lines()[row].len() {
+    if col.saturating_add(n) >= editor.lines()[row].len() {
         CursorMove::End
     } else {
         CursorMove::Jump(row as u16, (col + n) as u16)
@@ -615,7 +1295,7 @@ fn cursor_move_forward(editor: &TextArea, n: usize) -> CursorMove {
 fn cursor_move_down(editor: &TextArea, n: usize) -> CursorMove {
     let num_lines = editor.lines().len();
     let (row, col) = editor.cursor();
-    if row + n >= num_lines {
+    if row.saturating_add(n) >= num_lines {
         CursorMove::Bottom
     } else {
         CursorMove::Jump((row + n) as u16, col as u16)
@@ -645,6 +1325,26 @@ fn move_cursor_to_line_non_empty_start(editor: &mut TextArea) {
     }
 }
 
+/// Scrolls the view by `rows` and moves the cursor as many, which keeps it on the same
+/// screen line until the view reaches either end of the note.
+fn scroll_view(tab: &mut EditorTab, rows: isize, height: usize) {
+    let editor = &mut tab.editor;
+    let (row, col) = editor.cursor();
+    let last = editor.lines().len() - 1;
+    let (top, _) = tab.viewport;
+    let new_top = top
+        .saturating_add_signed(rows)
+        .min(last.saturating_sub(height.saturating_sub(1)));
+
+    let bottom = (new_top + height.max(1) - 1).min(last);
+    let row = row.saturating_add_signed(rows).clamp(new_top, bottom);
+
+    let scrolled = (new_top as isize - top as isize).clamp(i16::MIN as isize, i16::MAX as isize);
+    editor.scroll((scrolled as i16, 0));
+    editor.move_cursor(CursorMove::Jump(row as u16, col as u16));
+    tab.viewport.0 = top.saturating_add_signed(scrolled);
+}
+
 fn move_cursor_word_end(editor: &mut TextArea, n: usize) {
     for _ in 0..n {
         editor.move_cursor(CursorMove::WordEnd);
@@ -657,6 +1357,444 @@ fn move_cursor_word_back(editor: &mut TextArea, n: usize) {
     }
 }
 
+/// Column of the `n`th match of `find` in the cursor line.
+///
+/// A repeated find skips a `t` or `T` match right next to the cursor, so `;` keeps moving.
+fn find_char_column(editor: &TextArea, find: CharFind, n: usize, repeat: bool) -> Option<usize> {
+    let (row, col) = editor.cursor();
+    let line: Vec<char> = editor.lines()[row].chars().collect();
+    let skip = n.saturating_sub(1);
+    let matches = |i: &usize| line[*i] == find.target;
+
+    match find.kind {
+        FindKind::Find => (col + 1..line.len()).filter(matches).nth(skip),
+        FindKind::FindBack => (0..col).rev().filter(matches).nth(skip),
+        FindKind::Till => {
+            let start = if repeat { col + 2 } else { col + 1 };
+
+            (start..line.len()).filter(matches).nth(skip).map(|i| i - 1)
+        }
+        FindKind::TillBack => {
+            let end = if repeat { col.saturating_sub(1) } else { col };
+
+            (0..end).rev().filter(matches).nth(skip).map(|i| i + 1)
+        }
+    }
+}
+
+fn move_cursor_to_char(editor: &mut TextArea, find: CharFind, n: usize, repeat: bool) {
+    if let Some(col) = find_char_column(editor, find, n, repeat) {
+        let (row, _) = editor.cursor();
+        editor.move_cursor(CursorMove::Jump(row as u16, col as u16));
+    }
+}
+
+/// Selects what `d` or `y` with `find` covers, which includes the found column
+/// forwards and stops short of the cursor backwards, as in Vim.
+fn select_to_char(editor: &mut TextArea, find: CharFind, n: usize) -> bool {
+    let col = match find_char_column(editor, find, n, false) {
+        Some(col) => col,
+        None => return false,
+    };
+
+    let (row, cursor_col) = editor.cursor();
+    let (begin, end) = if find.kind.is_backward() {
+        (col, cursor_col)
+    } else {
+        (cursor_col, col + 1)
+    };
+    if begin >= end {
+        return false;
+    }
+
+    editor.move_cursor(CursorMove::Jump(row as u16, begin as u16));
+    editor.start_selection();
+    editor.move_cursor(CursorMove::Jump(row as u16, end as u16));
+
+    true
+}
+
+fn select_lines(editor: &mut TextArea, top: usize, bottom: usize) {
+    editor.move_cursor(CursorMove::Jump(top as u16, 0));
+    editor.start_selection();
+    editor.move_cursor(CursorMove::Jump(bottom as u16, 0));
+    editor.move_cursor(CursorMove::End);
+}
+
+/// Deletes `n` lines from the cursor, joining what is left.
+fn delete_lines(editor: &mut TextArea, n: usize) {
+    let (row, _) = editor.cursor();
+
+    editor.move_cursor(CursorMove::Head);
+    editor.start_selection();
+    let cursor_move = cursor_move_down(editor, n - 1);
+    editor.move_cursor(cursor_move);
+    editor.move_cursor(CursorMove::End);
+    editor.cut();
+
+    if row == 0 {
+        editor.move_cursor(CursorMove::Down);
+        editor.move_cursor(CursorMove::Head);
+        editor.delete_char();
+    } else {
+        editor.delete_char();
+        editor.move_cursor(CursorMove::Down);
+    }
+
+    move_cursor_to_line_non_empty_start(editor);
+}
+
+/// Cuts the text of `n` lines from the cursor, leaving an empty line to insert into.
+fn cut_lines(editor: &mut TextArea, n: usize) {
+    editor.move_cursor(CursorMove::Head);
+    editor.start_selection();
+    let cursor_move = cursor_move_down(editor, n - 1);
+    editor.move_cursor(cursor_move);
+    editor.move_cursor(CursorMove::End);
+    editor.cut();
+}
+
+/// Puts `width` spaces in front of each line which is not empty.
+fn indent_lines(editor: &mut TextArea, rows: RangeInclusive<usize>, width: usize) -> bool {
+    let top = *rows.start();
+    let mut changed = false;
+    for row in rows {
+        if editor.lines()[row].is_empty() {
+            continue;
+        }
+
+        editor.move_cursor(CursorMove::Jump(row as u16, 0));
+        editor.insert_str(" ".repeat(width));
+        changed = true;
+    }
+
+    editor.move_cursor(CursorMove::Jump(top as u16, 0));
+    move_cursor_to_line_non_empty_start(editor);
+
+    changed
+}
+
+/// Takes up to `width` columns of blanks off the start of each line, a tab being a whole indent.
+fn outdent_lines(editor: &mut TextArea, rows: RangeInclusive<usize>, width: usize) -> bool {
+    let yank = editor.yank_text();
+    let top = *rows.start();
+    let mut changed = false;
+    for row in rows {
+        let mut columns = 0;
+        let blanks = editor.lines()[row]
+            .chars()
+            .take_while(|c| match c {
+                ' ' if columns < width => {
+                    columns += 1;
+                    true
+                }
+                '\t' if columns < width => {
+                    columns = width;
+                    true
+                }
+                _ => false,
+            })
+            .count();
+        if blanks == 0 {
+            continue;
+        }
+
+        editor.move_cursor(CursorMove::Jump(row as u16, 0));
+        editor.delete_str(blanks);
+        changed = true;
+    }
+
+    editor.set_yank_text(yank);
+    editor.move_cursor(CursorMove::Jump(top as u16, 0));
+    move_cursor_to_line_non_empty_start(editor);
+
+    changed
+}
+
+/// Joins `n` lines from the cursor, putting a space in place of the indentation of
+/// each joined line. The cursor is left where the last two lines met.
+fn join_lines(editor: &mut TextArea, n: usize) -> bool {
+    let yank = editor.yank_text();
+    let (row, _) = editor.cursor();
+    let mut joined = false;
+    for _ in 1..n {
+        let lines = editor.lines();
+        if row + 1 >= lines.len() {
+            break;
+        }
+
+        let (line, next) = (&lines[row], &lines[row + 1]);
+        let indent = next.chars().take_while(|c| c.is_whitespace()).count();
+        let rest = next.trim_start();
+        let space = !line.is_empty() && !line.ends_with(' ') && !rest.is_empty();
+        let space = space && !rest.starts_with(')');
+
+        editor.move_cursor(CursorMove::Jump(row as u16, 0));
+        editor.move_cursor(CursorMove::End);
+        let (_, col) = editor.cursor();
+        editor.delete_next_char();
+        editor.delete_str(indent);
+        if space {
+            editor.insert_char(' ');
+        }
+
+        editor.move_cursor(CursorMove::Jump(row as u16, col as u16));
+        joined = true;
+    }
+
+    editor.set_yank_text(yank);
+
+    joined
+}
+
+/// The text from `begin` up to but not including `end`.
+fn range_text(editor: &TextArea, begin: (usize, usize), end: (usize, usize)) -> String {
+    (begin.0..=end.0)
+        .map(|row| {
+            let from = if row == begin.0 { begin.1 } else { 0 };
+            let to = if row == end.0 { end.1 } else { usize::MAX };
+
+            editor.lines()[row]
+                .chars()
+                .skip(from)
+                .take(to.saturating_sub(from))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Writes `text` in place of the range, keeping what has been yanked.
+fn replace_range(editor: &mut TextArea, begin: (usize, usize), end: (usize, usize), text: &str) {
+    let yank = editor.yank_text();
+
+    editor.move_cursor(CursorMove::Jump(begin.0 as u16, begin.1 as u16));
+    editor.start_selection();
+    editor.move_cursor(CursorMove::Jump(end.0 as u16, end.1 as u16));
+    editor.cut();
+    editor.insert_str(text);
+
+    editor.set_yank_text(yank);
+}
+
+/// Leaves the cursor at `begin`, returning whether any letter has changed.
+fn change_case(
+    editor: &mut TextArea,
+    begin: (usize, usize),
+    end: (usize, usize),
+    kind: CaseKind,
+) -> bool {
+    let text = range_text(editor, begin, end);
+    let changed = kind.apply(&text);
+    if changed != text {
+        replace_range(editor, begin, end, &changed);
+    }
+
+    editor.move_cursor(CursorMove::Jump(begin.0 as u16, begin.1 as u16));
+
+    changed != text
+}
+
+/// What `g~`, `gu` or `gU` followed by a motion or text object covers.
+fn case_range(
+    editor: &mut TextArea,
+    transition: &NormalModeTransition,
+    screen: Range<usize>,
+) -> Option<((usize, usize), (usize, usize))> {
+    use NormalModeTransition::*;
+
+    let (row, col) = editor.cursor();
+    let line_end = |editor: &TextArea, n: usize| {
+        let bottom = row.saturating_add(n - 1).min(editor.lines().len() - 1);
+
+        (bottom, editor.lines()[bottom].chars().count())
+    };
+
+    let range = match *transition {
+        CaseLines(_, n) => ((row, 0), line_end(editor, n)),
+        CaseLineEnd(_, n) => ((row, col), line_end(editor, n)),
+        CaseLineStart(_) => ((row, 0), (row, col)),
+        CaseWordEnd(_, n) => {
+            move_cursor_word_end(editor, n);
+            editor.move_cursor(CursorMove::Forward);
+
+            ((row, col), editor.cursor())
+        }
+        CaseWordBack(_, n) => {
+            move_cursor_word_back(editor, n);
+
+            (editor.cursor(), (row, col))
+        }
+        CaseToChar(_, find, n) => {
+            if !select_to_char(editor, find, n) {
+                return None;
+            }
+
+            let range = editor.selection_range();
+            editor.cancel_selection();
+            range?
+        }
+        CaseTextObject(..) | CaseMotion(..) => {
+            let range = match *transition {
+                CaseTextObject(_, object, n) => text_object_range(editor, object, n),
+                CaseMotion(_, motion, n) => motion_range(editor, motion, n, screen),
+                _ => None,
+            };
+            let ObjectRange {
+                begin,
+                end,
+                linewise,
+            } = range?;
+
+            match linewise {
+                true => ((begin.0, 0), (end.0, editor.lines()[end.0].chars().count())),
+                false => (begin, end),
+            }
+        }
+        _ => return None,
+    };
+
+    (range.0 != range.1).then_some(range)
+}
+
+/// Types `key` over the character under the cursor, returning whether the text has changed.
+fn overwrite(editor: &mut TextArea, overwritten: &mut Vec<Option<char>>, key: KeyEvent) -> bool {
+    let (row, col) = editor.cursor();
+    match key {
+        KeyEvent::Char(c) => {
+            let old = editor.lines()[row].chars().nth(col);
+            if old.is_some() {
+                editor.delete_next_char();
+            }
+
+            editor.insert_char(c);
+            overwritten.push(old);
+            true
+        }
+        KeyEvent::Enter => {
+            editor.insert_newline();
+            overwritten.push(None);
+            true
+        }
+        KeyEvent::Backspace => match overwritten.pop() {
+            Some(Some(old)) => {
+                editor.move_cursor(CursorMove::Back);
+                editor.delete_next_char();
+                editor.insert_char(old);
+                editor.move_cursor(CursorMove::Back);
+                true
+            }
+            Some(None) => editor.delete_char(),
+            None => {
+                if col > 0 {
+                    editor.move_cursor(CursorMove::Back);
+                }
+
+                false
+            }
+        },
+        key => {
+            overwritten.clear();
+            editor.input(to_input(key))
+        }
+    }
+}
+
+/// The columns of the block on each of its lines, one line per row.
+fn block_text(editor: &TextArea, begin: (usize, usize), end: (usize, usize)) -> String {
+    editor.lines()[begin.0..=end.0]
+        .iter()
+        .map(|line| {
+            line.chars()
+                .skip(begin.1)
+                .take(end.1 + 1 - begin.1)
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Deletes the block into the yank, leaving the cursor at its top left corner.
+fn delete_block(editor: &mut TextArea, begin: (usize, usize), end: (usize, usize)) {
+    let text = block_text(editor, begin, end);
+    for row in begin.0..=end.0 {
+        let len = editor.lines()[row].chars().count();
+        if len > begin.1 {
+            editor.move_cursor(CursorMove::Jump(row as u16, begin.1 as u16));
+            editor.delete_str((end.1 + 1).min(len) - begin.1);
+        }
+    }
+
+    editor.set_yank_text(text);
+    editor.move_cursor(CursorMove::Jump(begin.0 as u16, begin.1 as u16));
+}
+
+fn pad_line(editor: &mut TextArea, row: usize, col: usize) {
+    let len = editor.lines()[row].chars().count();
+    if len < col {
+        editor.move_cursor(CursorMove::Jump(row as u16, len as u16));
+        editor.insert_str(" ".repeat(col - len));
+    }
+}
+
+/// Moves to `col` of the first line of the block, where the text to copy is typed.
+fn start_block_insert(
+    editor: &mut TextArea,
+    rows: RangeInclusive<usize>,
+    col: usize,
+    pad: bool,
+) -> BlockInsert {
+    let row = *rows.start();
+    if pad {
+        pad_line(editor, row, col);
+    }
+    editor.move_cursor(CursorMove::Jump(row as u16, col as u16));
+
+    let (row, col) = editor.cursor();
+    BlockInsert {
+        row,
+        col,
+        len: editor.lines()[row].chars().count(),
+        lines: editor.lines().len(),
+        rows: row + 1..=*rows.end(),
+        pad,
+    }
+}
+
+/// Copies what was typed on the first line of the block to the others, unless
+/// the insert went beyond that line. Returns whether any line has changed.
+fn apply_block_insert(editor: &mut TextArea, insert: BlockInsert) -> bool {
+    let (row, _) = editor.cursor();
+    if row != insert.row || editor.lines().len() != insert.lines {
+        return false;
+    }
+
+    let len = editor.lines()[row].chars().count();
+    if len <= insert.len {
+        return false;
+    }
+
+    let text: String = editor.lines()[row]
+        .chars()
+        .skip(insert.col)
+        .take(len - insert.len)
+        .collect();
+    for row in insert.rows {
+        if insert.pad {
+            pad_line(editor, row, insert.col);
+        } else if editor.lines()[row].chars().count() < insert.col {
+            continue;
+        }
+
+        editor.move_cursor(CursorMove::Jump(row as u16, insert.col as u16));
+        editor.insert_str(&text);
+    }
+
+    editor.move_cursor(CursorMove::Jump(insert.row as u16, insert.col as u16));
+
+    true
+}
+
 fn reselect_for_yank(editor: &mut TextArea) {
     let (begin, end) = match editor.selection_range() {
         None => return,
@@ -0,0 +1,162 @@
+use {
+    super::text_object::{bracket, is_blank_line, sentence_end, ObjectRange, Text},
+    glues_core::state::notebook::Motion,
+    std::ops::Range,
+    tui_textarea::TextArea,
+};
+
+/// Where `motion` moves the cursor to, `screen` being the rows of the editor on screen.
+pub(super) fn motion_target(
+    editor: &TextArea,
+    motion: Motion,
+    n: usize,
+    screen: Range<usize>,
+) -> Option<(usize, usize)> {
+    let (row, col) = editor.cursor();
+    let lines = editor.lines();
+    let last = lines.len() - 1;
+    let blank = |row: usize| lines[row].trim().is_empty();
+    let first_non_blank = |row: usize| {
+        let col = lines[row].chars().take_while(|c| c.is_whitespace()).count();
+
+        (row, col.min(lines[row].chars().count().saturating_sub(1)))
+    };
+
+    let target = match motion {
+        Motion::ParagraphForward => {
+            let mut row = row;
+            for _ in 0..n {
+                row = match (row + 1..=last).find(|i| blank(*i) && !blank(i - 1)) {
+                    Some(row) => row,
+                    None => return Some((last, lines[last].chars().count())),
+                };
+            }
+
+            (row, 0)
+        }
+        Motion::ParagraphBack => {
+            let mut row = row;
+            for _ in 0..n {
+                row = match (0..row).rev().find(|i| blank(*i) && !blank(i + 1)) {
+                    Some(row) => row,
+                    None => return Some((0, 0)),
+                };
+            }
+
+            (row, 0)
+        }
+        Motion::SentenceForward | Motion::SentenceBack => {
+            let text = Text::new(lines);
+            let offset = text.offset(row, col);
+            let starts = sentence_starts(&text.chars);
+            let start = match motion {
+                Motion::SentenceForward => starts
+                    .into_iter()
+                    .filter(|start| *start > offset)
+                    .nth(n - 1)
+                    .unwrap_or(text.chars.len()),
+                _ => starts
+                    .into_iter()
+                    .rev()
+                    .filter(|start| *start < offset)
+                    .nth(n - 1)
+                    .unwrap_or(0),
+            };
+
+            text.position(start)
+        }
+        Motion::MatchBracket => {
+            let text = Text::new(lines);
+            let offset = match_bracket(&text.chars, text.offset(row, col))?;
+
+            text.position(offset)
+        }
+        Motion::Percent => {
+            let row = (n.min(100) * lines.len()).div_ceil(100).max(1) - 1;
+
+            first_non_blank(row)
+        }
+        Motion::ScreenTop | Motion::ScreenMiddle | Motion::ScreenBottom => {
+            let top = screen.start;
+            let bottom = screen.end.saturating_sub(1).max(top).min(last);
+            let row = match motion {
+                Motion::ScreenTop => top.saturating_add(n - 1).min(bottom),
+                Motion::ScreenBottom => bottom.saturating_sub(n - 1).max(top),
+                _ => top + (bottom - top) / 2,
+            };
+
+            first_non_blank(row)
+        }
+    };
+
+    Some(target)
+}
+
+/// What an operator takes with `motion`: whole lines for a linewise motion,
+/// otherwise up to the target, which `%` includes.
+pub(super) fn motion_range(
+    editor: &TextArea,
+    motion: Motion,
+    n: usize,
+    screen: Range<usize>,
+) -> Option<ObjectRange> {
+    let cursor = editor.cursor();
+    let target = motion_target(editor, motion, n, screen)?;
+    let (begin, end) = (cursor.min(target), cursor.max(target));
+
+    if motion.is_linewise() {
+        return Some(ObjectRange {
+            begin: (begin.0, 0),
+            end: (end.0, editor.lines()[end.0].chars().count()),
+            linewise: true,
+        });
+    }
+
+    let end = match motion {
+        Motion::MatchBracket => (end.0, end.1 + 1),
+        _ => end,
+    };
+
+    Some(ObjectRange {
+        begin,
+        end,
+        linewise: false,
+    })
+}
+
+/// Offsets where sentences start: after the end of another, at an empty line, and at
+/// the first word after empty lines or the start of the text.
+fn sentence_starts(chars: &[char]) -> Vec<usize> {
+    let next_word = |from: usize| (from..chars.len()).find(|i| !chars[*i].is_whitespace());
+
+    let mut starts: Vec<usize> = next_word(0).into_iter().collect();
+    for i in 0..chars.len() {
+        if is_blank_line(chars, i) {
+            starts.push(i + 1);
+            starts.extend(next_word(i + 1));
+        } else if let Some(end) = sentence_end(chars, i) {
+            starts.extend(next_word(end));
+        }
+    }
+
+    starts.sort_unstable();
+    starts.dedup();
+    starts
+}
+
+/// The bracket matching the first one at or after the offset in its line.
+fn match_bracket(chars: &[char], offset: usize) -> Option<usize> {
+    const PAIRS: [(char, char); 3] = [('(', ')'), ('[', ']'), ('{', '}')];
+
+    let (i, (open, close)) = (offset..chars.len())
+        .take_while(|i| chars[*i] != '\n')
+        .find_map(|i| {
+            PAIRS
+                .into_iter()
+                .find(|(open, close)| chars[i] == *open || chars[i] == *close)
+                .map(|pair| (i, pair))
+        })?;
+    let (begin, end) = bracket(chars, i, open, close, 1)?;
+
+    Some(if chars[i] == open { end } else { begin })
+}
@@ -0,0 +1,372 @@
+use {
+    glues_core::state::notebook::{ObjectKind, ObjectScope, TextObject},
+    tui_textarea::TextArea,
+};
+
+/// The text a text object or motion covers, from `begin` up to but not including `end`.
+pub(super) struct ObjectRange {
+    pub begin: (usize, usize),
+    pub end: (usize, usize),
+    /// Paragraphs are taken as whole lines, from the row of `begin` to the row of `end`.
+    pub linewise: bool,
+}
+
+pub(super) fn text_object_range(
+    editor: &TextArea,
+    object: TextObject,
+    n: usize,
+) -> Option<ObjectRange> {
+    let around = object.scope == ObjectScope::Around;
+    let (row, col) = editor.cursor();
+    let line: Vec<char> = editor.lines()[row].chars().collect();
+    let charwise = |(begin, end): (usize, usize)| ObjectRange {
+        begin: (row, begin),
+        end: (row, end),
+        linewise: false,
+    };
+
+    match object.kind {
+        ObjectKind::Word => word(&line, col, n, around, false).map(charwise),
+        ObjectKind::BigWord => word(&line, col, n, around, true).map(charwise),
+        ObjectKind::Quote(quote) => quoted(&line, col, quote, around).map(charwise),
+        ObjectKind::Paragraph => {
+            let (top, bottom) = paragraph(editor.lines(), row, n, around);
+
+            Some(ObjectRange {
+                begin: (top, 0),
+                end: (bottom, editor.lines()[bottom].chars().count()),
+                linewise: true,
+            })
+        }
+        ObjectKind::Sentence => {
+            let text = Text::new(editor.lines());
+            let (begin, end) = sentence(&text.chars, text.offset(row, col), n, around)?;
+
+            Some(text.range(begin, end))
+        }
+        ObjectKind::Bracket(open, close) => {
+            let text = Text::new(editor.lines());
+            let (begin, end) = bracket(&text.chars, text.offset(row, col), open, close, n)?;
+            if around {
+                return Some(text.range(begin, end + 1));
+            }
+
+            let (begin, end, linewise) = inner_bracket(&text.chars, begin, end);
+
+            Some(ObjectRange {
+                linewise,
+                ..text.range(begin, end)
+            })
+        }
+    }
+}
+
+/// The note as one run of characters, for objects which may span lines.
+pub(super) struct Text {
+    pub chars: Vec<char>,
+    line_starts: Vec<usize>,
+}
+
+impl Text {
+    pub(super) fn new(lines: &[String]) -> Self {
+        let mut chars = Vec::new();
+        let mut line_starts = Vec::with_capacity(lines.len());
+        for line in lines {
+            if !line_starts.is_empty() {
+                chars.push('\n');
+            }
+
+            line_starts.push(chars.len());
+            chars.extend(line.chars());
+        }
+
+        Self { chars, line_starts }
+    }
+
+    pub(super) fn offset(&self, row: usize, col: usize) -> usize {
+        (self.line_starts[row] + col).min(self.chars.len().saturating_sub(1))
+    }
+
+    pub(super) fn position(&self, offset: usize) -> (usize, usize) {
+        let row = self.line_starts.partition_point(|start| *start <= offset) - 1;
+
+        (row, offset - self.line_starts[row])
+    }
+
+    fn range(&self, begin: usize, end: usize) -> ObjectRange {
+        ObjectRange {
+            begin: self.position(begin),
+            end: self.position(end),
+            linewise: false,
+        }
+    }
+}
+
+/// Whether the line after the line break at `i` is empty.
+pub(super) fn is_blank_line(chars: &[char], i: usize) -> bool {
+    chars[i] == '\n' && chars.get(i + 1) == Some(&'\n')
+}
+
+/// The end of a sentence whose punctuation is at `i`. A sentence ends with `.`, `!` or `?`,
+/// and any closing quotes or brackets, followed by a blank or the end of the text.
+pub(super) fn sentence_end(chars: &[char], i: usize) -> Option<usize> {
+    if !matches!(chars[i], '.' | '!' | '?') {
+        return None;
+    }
+
+    let end = (i + 1..chars.len())
+        .find(|j| !matches!(chars[*j], ')' | ']' | '"' | '\''))
+        .unwrap_or(chars.len());
+
+    (end == chars.len() || chars[end].is_whitespace()).then_some(end)
+}
+
+fn word_class(c: char, big: bool) -> u8 {
+    if c.is_whitespace() {
+        0
+    } else if big || c.is_alphanumeric() || c == '_' {
+        1
+    } else {
+        2
+    }
+}
+
+/// `iw` takes `n` runs of word, punctuation or blank characters, `aw` takes `n` words
+/// with the blanks after them, or before them at the end of the line.
+fn word(line: &[char], col: usize, n: usize, around: bool, big: bool) -> Option<(usize, usize)> {
+    if line.is_empty() {
+        return None;
+    }
+
+    let col = col.min(line.len() - 1);
+    let class = |i: usize| word_class(line[i], big);
+    let run_end = |i: usize| {
+        (i..line.len())
+            .find(|j| class(*j) != class(i))
+            .unwrap_or(line.len())
+    };
+    let blank = |i: usize| class(i) == 0;
+
+    let mut begin = (0..col)
+        .rev()
+        .find(|i| class(*i) != class(col))
+        .map_or(0, |i| i + 1);
+    let mut end = begin;
+    let mut trailing = false;
+    for _ in 0..n {
+        if end >= line.len() {
+            break;
+        }
+
+        end = run_end(end);
+        if !around {
+            continue;
+        }
+
+        if blank(col) {
+            if end < line.len() {
+                end = run_end(end);
+            }
+        } else {
+            trailing = end < line.len() && blank(end);
+            if trailing {
+                end = run_end(end);
+            }
+        }
+    }
+
+    if around && !blank(col) && !trailing {
+        while begin > 0 && blank(begin - 1) {
+            begin -= 1;
+        }
+    }
+
+    Some((begin, end))
+}
+
+/// Quotes pair up from the start of the line, skipping those escaped with a backslash.
+/// Outside any pair, the next quoted text after the cursor is taken.
+fn quoted(line: &[char], col: usize, quote: char, around: bool) -> Option<(usize, usize)> {
+    let quotes: Vec<usize> = (0..line.len())
+        .filter(|i| line[*i] == quote && (*i == 0 || line[i - 1] != '\\'))
+        .collect();
+
+    let pair = match quotes.iter().position(|i| *i == col) {
+        Some(k) => k - k % 2,
+        None => (0..quotes.len() / 2)
+            .map(|pair| pair * 2)
+            .find(|k| quotes[*k] < col && col < quotes[k + 1])
+            .or_else(|| quotes.iter().position(|i| *i > col))?,
+    };
+    let (open, close) = (*quotes.get(pair)?, *quotes.get(pair + 1)?);
+
+    if !around {
+        return Some((open + 1, close));
+    }
+
+    let blank = |i: usize| line[i] == ' ' || line[i] == '\t';
+    let end = (close + 1..line.len())
+        .find(|i| !blank(*i))
+        .unwrap_or(line.len());
+    if end > close + 1 {
+        return Some((open, end));
+    }
+
+    let begin = (0..open).rev().find(|i| !blank(*i)).map_or(0, |i| i + 1);
+
+    Some((begin, close + 1))
+}
+
+/// Consecutive lines which are all blank or all not, `n` of such runs for `ip`.
+/// `ap` adds the blank lines after the paragraph, or before it when there are none.
+fn paragraph(lines: &[String], row: usize, n: usize, around: bool) -> (usize, usize) {
+    let blank = |row: usize| lines[row].trim().is_empty();
+    let run_end = |row: usize| {
+        (row..lines.len())
+            .find(|i| blank(*i) != blank(row))
+            .unwrap_or(lines.len())
+            - 1
+    };
+
+    let mut top = (0..row)
+        .rev()
+        .find(|i| blank(*i) != blank(row))
+        .map_or(0, |i| i + 1);
+    let mut bottom = run_end(row);
+    for _ in 1..n {
+        if bottom + 1 < lines.len() {
+            bottom = run_end(bottom + 1);
+        }
+    }
+
+    if around {
+        if bottom + 1 < lines.len() {
+            bottom = run_end(bottom + 1);
+        } else if !blank(row) {
+            while top > 0 && blank(top - 1) {
+                top -= 1;
+            }
+        }
+    }
+
+    (top, bottom)
+}
+
+/// Sentences end as `sentence_end` finds them, and at empty lines.
+fn sentence(chars: &[char], offset: usize, n: usize, around: bool) -> Option<(usize, usize)> {
+    if chars.is_empty() {
+        return None;
+    }
+
+    let blank_line = |i: usize| is_blank_line(chars, i);
+    let end_at = |i: usize| sentence_end(chars, i);
+    let next_end = |from: usize| {
+        (from..chars.len())
+            .find_map(|i| match blank_line(i) {
+                true => Some(i),
+                false => end_at(i),
+            })
+            .unwrap_or(chars.len())
+    };
+    let skip_blanks = |from: usize| {
+        (from..chars.len())
+            .find(|i| !chars[*i].is_whitespace() || blank_line(*i))
+            .unwrap_or(chars.len())
+    };
+
+    let start = (0..offset)
+        .rev()
+        .find_map(|i| match blank_line(i) {
+            true => Some(i + 2),
+            false => end_at(i).filter(|end| *end <= offset),
+        })
+        .unwrap_or(0);
+    let begin = skip_blanks(start).min(offset);
+
+    let mut end = begin;
+    for _ in 0..n {
+        if end >= chars.len() {
+            break;
+        }
+
+        end = next_end(skip_blanks(end));
+    }
+
+    if !around {
+        return Some((begin, end));
+    }
+
+    let trailing = skip_blanks(end);
+    if trailing > end {
+        return Some((begin, trailing));
+    }
+
+    Some((start.min(begin), end))
+}
+
+/// Offsets of the `n`th pair of brackets around the offset, where an opening bracket
+/// counts as the first and a closing one stands for its pair.
+pub(super) fn bracket(
+    chars: &[char],
+    offset: usize,
+    open: char,
+    close: char,
+    n: usize,
+) -> Option<(usize, usize)> {
+    let mut levels = match chars.get(offset) {
+        Some(c) if *c == open => n - 1,
+        _ => n,
+    };
+
+    let mut depth = 0;
+    let begin = match levels {
+        0 => offset,
+        _ => (0..offset).rev().find(|i| {
+            if chars[*i] == close {
+                depth += 1;
+            } else if chars[*i] == open && depth > 0 {
+                depth -= 1;
+            } else if chars[*i] == open {
+                levels -= 1;
+            }
+
+            levels == 0
+        })?,
+    };
+
+    depth = 0;
+    let end = (begin + 1..chars.len()).find(|i| {
+        if chars[*i] == open {
+            depth += 1;
+        } else if chars[*i] == close {
+            if depth == 0 {
+                return true;
+            }
+            depth -= 1;
+        }
+
+        false
+    })?;
+
+    Some((begin, end))
+}
+
+/// The content between the brackets. When the brackets are on lines of their own,
+/// it is the whole lines between them, so a block keeps its braces.
+fn inner_bracket(chars: &[char], begin: usize, end: usize) -> (usize, usize, bool) {
+    let mut inner_begin = begin + 1;
+    let opened_line = chars.get(inner_begin) == Some(&'\n');
+    if opened_line {
+        inner_begin += 1;
+    }
+
+    let line_start = (inner_begin..end).rev().find(|i| chars[*i] == '\n');
+    let inner_end = match line_start {
+        Some(i) if chars[i + 1..end].iter().all(|c| c.is_whitespace()) => i,
+        _ => end,
+    };
+
+    let linewise = opened_line && inner_end < end && inner_begin <= inner_end;
+
+    (inner_begin.min(inner_end), inner_end, linewise)
+}
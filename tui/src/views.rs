@@ -1,4 +1,5 @@
 pub mod body;
+pub mod command_line;
 pub mod dialog;
 pub mod keymap;
 pub mod statusbar;
@@ -8,7 +8,7 @@ use {
 
 pub fn draw(frame: &mut Frame, area: Rect, context: &mut Context) {
     match context.state {
-        ContextState::Entry => entry::draw(frame, area, &mut context.entry),
+        ContextState::Entry => entry::draw(frame, area, &mut context.entry, &context.settings),
         ContextState::Notebook => notebook::draw(frame, area, context),
     }
 }
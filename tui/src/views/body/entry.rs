@@ -1,44 +1,104 @@
 use {
-    crate::context::{entry::MENU_ITEMS, EntryContext},
+    crate::{
+        context::{
+            entry::{MENU_ITEMS, RECENT_KEYS},
+            EntryContext,
+        },
+        keymap::Mode,
+        locator,
+        settings::Settings,
+    },
     ratatui::{
         layout::{Alignment, Constraint::Length, Flex, Layout, Rect},
-        style::{Color, Style, Stylize},
-        widgets::{Block, HighlightSpacing, List, ListDirection, Padding},
+        text::{Line, Span},
+        widgets::{Block, HighlightSpacing, List, ListDirection, Padding, Paragraph},
         Frame,
     },
     tui_big_text::BigText,
 };
 
-pub fn draw(frame: &mut Frame, area: Rect, context: &mut EntryContext) {
+pub fn draw(frame: &mut Frame, area: Rect, context: &mut EntryContext, settings: &Settings) {
+    let Settings { keymap, theme, .. } = settings;
+    let recent_width = context
+        .recent
+        .iter()
+        .map(|recent| locator::redact(&recent.locator).chars().count() as u16 + 36)
+        .max()
+        .unwrap_or_default()
+        .max(38)
+        .min(area.width);
+    let [recent_area] = Layout::horizontal([Length(recent_width)])
+        .flex(Flex::Center)
+        .areas(area);
     let [area] = Layout::horizontal([Length(38)])
         .flex(Flex::Center)
         .areas(area);
-    let [title_area, area] = Layout::vertical([Length(9), Length(12)])
+    let recent_height = if context.recent.is_empty() {
+        0
+    } else {
+        context.recent.len() as u16 + 2
+    };
+    let [title_area, area, _] = Layout::vertical([Length(9), Length(12), Length(recent_height)])
         .flex(Flex::Center)
         .areas(area);
+    let [_, _, recent_area] = Layout::vertical([Length(9), Length(12), Length(recent_height)])
+        .flex(Flex::Center)
+        .areas(recent_area);
 
     let title = BigText::builder()
-        .lines(vec!["Glues".dark_gray().into()])
+        .lines(vec![Line::styled("Glues", theme.logo)])
         .build();
     let block = Block::bordered()
+        .border_style(theme.border)
         .padding(Padding::new(2, 2, 1, 1))
         .title("Open Notes")
         .title_alignment(Alignment::Center);
 
-    let items = MENU_ITEMS.into_iter().map(|name| {
+    let items = MENU_ITEMS.into_iter().map(|item| {
+        let name = keymap.relabel(Mode::Entry, item);
+
         if name.ends_with("CSV") || name.ends_with("JSON") {
-            name.dark_gray().dim()
+            Line::styled(name, theme.disabled)
         } else {
-            name.into()
+            Line::raw(name)
         }
     });
     let list = List::new(items)
         .block(block)
-        .highlight_style(Style::new().fg(Color::White).bg(Color::Blue))
+        .highlight_style(theme.selection)
         .highlight_symbol(" ")
         .highlight_spacing(HighlightSpacing::Always)
         .direction(ListDirection::TopToBottom);
 
     frame.render_widget(title, title_area);
     frame.render_stateful_widget(list, area, &mut context.list_state);
+
+    if context.recent.is_empty() {
+        return;
+    }
+
+    let block = Block::bordered()
+        .border_style(theme.border)
+        .padding(Padding::horizontal(2))
+        .title("Recent")
+        .title_alignment(Alignment::Center);
+    let lines = context
+        .recent
+        .iter()
+        .zip(RECENT_KEYS)
+        .map(|(recent, key)| {
+            Line::from(vec![
+                keymap.relabel(Mode::Entry, &format!("[{key}] ")).into(),
+                Span::styled(format!("{:<8}", recent.kind), theme.hint),
+                locator::redact(&recent.locator).into(),
+                Span::styled(
+                    format!("  {}", recent.opened_at.format("%Y-%m-%d %H:%M")),
+                    theme.hint,
+                ),
+            ])
+        })
+        .collect::<Vec<_>>();
+    let recent = Paragraph::new(lines).block(block);
+
+    frame.render_widget(recent, recent_area);
 }
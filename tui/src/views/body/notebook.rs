@@ -19,9 +19,15 @@ pub fn draw(frame: &mut Frame, area: Rect, context: &mut Context) {
         return;
     }
 
-    let horizontal = Layout::horizontal([Length(45), Percentage(100)]);
+    let width = context.settings.browser.width;
+    let horizontal = Layout::horizontal([Length(width), Percentage(100)]);
     let [note_tree_area, editor_area] = horizontal.areas(area);
 
-    note_tree::draw(frame, note_tree_area, &mut context.notebook);
+    note_tree::draw(
+        frame,
+        note_tree_area,
+        &mut context.notebook,
+        &context.settings.theme,
+    );
     editor::draw(frame, editor_area, context);
 }
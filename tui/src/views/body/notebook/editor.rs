@@ -1,29 +1,37 @@
 use {
-    crate::context::{notebook::ContextState, Context},
+    crate::{
+        context::{
+            notebook::{ContextState, Visual},
+            Context,
+        },
+        settings::Autosave,
+    },
+    glues_core::state::notebook::VisualKind,
     ratatui::{
         layout::Rect,
-        style::{Style, Stylize},
-        text::Line,
+        style::Style,
+        text::{Line, Span},
         widgets::{Block, Padding},
         Frame,
     },
     throbber_widgets_tui::Throbber,
-    tui_textarea::TextArea,
+    tui_textarea::{CursorMove, TextArea},
 };
 
 pub fn draw(frame: &mut Frame, area: Rect, context: &mut Context) {
+    let theme = context.settings.theme.clone();
     let title = if let Some(tab_index) = context.notebook.tab_index {
         let mut title = vec!["[".into()];
         for (i, tab) in context.notebook.tabs.iter().enumerate() {
             let name = tab.note.name.clone();
             let name = if i == tab_index {
                 if context.notebook.state.is_editor() {
-                    name.white().on_blue()
+                    Span::styled(name, theme.tab_active)
                 } else {
-                    name.white().on_dark_gray()
+                    Span::styled(name, theme.tab_unfocused)
                 }
             } else {
-                name.dark_gray()
+                Span::styled(name, theme.tab)
             };
 
             if i != 0 {
@@ -35,24 +43,30 @@ pub fn draw(frame: &mut Frame, area: Rect, context: &mut Context) {
 
         Line::from(title)
     } else {
-        Line::from("[Editor]".dark_gray())
+        Line::styled("[Editor]", theme.hint)
     };
 
-    let block = Block::bordered().title(title);
-    let block = match (
-        context.last_log.as_ref(),
-        context.notebook.tabs.iter().any(|tab| tab.dirty),
-    ) {
-        (_, true) => {
-            let throbber = Throbber::default()
-                .label("Saving...")
-                .style(Style::default().yellow());
+    let block = Block::bordered().border_style(theme.border).title(title);
+    let dirty = context
+        .notebook
+        .tab_index
+        .and_then(|i| context.notebook.tabs.get(i))
+        .map(|tab| tab.dirty);
+    let block = match (context.last_log.as_ref(), dirty) {
+        (_, Some(true)) if context.settings.editor.autosave == Autosave::Idle => {
+            let throbber = Throbber::default().label("Saving...").style(theme.throbber);
             block.title_bottom(Line::from(throbber).right_aligned())
         }
-        (Some((log, _)), false) => {
-            block.title_bottom(Line::from(log.clone().green()).right_aligned())
+        (_, Some(true)) => {
+            block.title_bottom(Line::styled("Modified", theme.warning).right_aligned())
+        }
+        (Some((log, _)), _) => {
+            block.title_bottom(Line::styled(log.clone(), theme.log).right_aligned())
         }
-        (None, false) => block,
+        (None, Some(false)) => {
+            block.title_bottom(Line::styled("Saved", theme.hint).right_aligned())
+        }
+        (None, None) => block,
     }
     .padding(if context.notebook.show_line_number {
         Padding::ZERO
@@ -60,8 +74,21 @@ pub fn draw(frame: &mut Frame, area: Rect, context: &mut Context) {
         Padding::left(1)
     });
 
+    let inner = block.inner(area);
+    context.notebook.editor_height = inner.height as usize;
+
     let show_line_number = context.notebook.show_line_number;
+    let tab_width = context.settings.editor.tab_width;
     let state = context.notebook.state;
+    let visual = context.notebook.visual;
+    let (pending_scroll, mut viewport) = match context
+        .notebook
+        .tab_index
+        .and_then(|i| context.notebook.tabs.get_mut(i))
+    {
+        Some(tab) => (tab.pending_scroll.take(), tab.viewport),
+        None => (None, (0, 0)),
+    };
     let mut editor = TextArea::from("Welcome to Glues :D".lines());
     let editor = if context.notebook.tab_index.is_some() {
         context.notebook.get_editor_mut()
@@ -70,24 +97,122 @@ pub fn draw(frame: &mut Frame, area: Rect, context: &mut Context) {
     };
 
     editor.set_block(block);
+    editor.set_tab_length(tab_width);
 
     let (cursor_style, cursor_line_style) = match state {
         ContextState::EditorNormalMode { .. }
         | ContextState::EditorInsertMode
-        | ContextState::EditorVisualMode => (
-            Style::default().white().on_blue(),
-            Style::default().underlined(),
-        ),
+        | ContextState::EditorVisualMode
+        | ContextState::EditorSearchMode => (theme.cursor, theme.cursor_line),
         _ => (Style::default(), Style::default()),
     };
 
     editor.set_cursor_style(cursor_style);
+    editor.set_search_style(theme.search);
     editor.set_cursor_line_style(cursor_line_style);
     if show_line_number {
-        editor.set_line_number_style(Style::default().dark_gray().dim());
+        editor.set_line_number_style(theme.line_number);
     } else {
         editor.remove_line_number();
     }
 
+    // the viewport of a restored tab is only sized once it is rendered
+    if let Some(top) = pending_scroll {
+        let (row, col) = editor.cursor();
+        let rows = (top as isize - viewport.0 as isize).clamp(i16::MIN as isize, i16::MAX as isize);
+        editor.scroll((rows as i16, 0));
+        editor.move_cursor(CursorMove::Jump(row as u16, col as u16));
+        viewport.0 = viewport.0.saturating_add_signed(rows);
+    }
+
+    let viewport = next_viewport(editor, viewport, inner);
     frame.render_widget(&*editor, area);
+
+    match visual {
+        Some(visual) if state == ContextState::EditorVisualMode => {
+            if visual.kind != VisualKind::Charwise {
+                highlight_visual(frame, inner, editor, viewport, visual);
+            }
+        }
+        _ => {}
+    }
+
+    if let Some(tab) = context
+        .notebook
+        .tab_index
+        .and_then(|i| context.notebook.tabs.get_mut(i))
+    {
+        tab.viewport = viewport;
+    }
+}
+
+/// Where `TextArea` scrolls to when rendered in `inner`, which is as little as it takes
+/// to show the cursor, its column counted past the line numbers.
+fn next_viewport(editor: &TextArea, (top, left): (usize, usize), inner: Rect) -> (usize, usize) {
+    let next = |prev: usize, cursor: usize, len: usize| {
+        if cursor < prev {
+            cursor
+        } else if prev + len <= cursor {
+            cursor + 1 - len
+        } else {
+            prev
+        }
+    };
+
+    let (row, mut col) = editor.cursor();
+    if editor.line_number_style().is_some() {
+        let gutter = editor.lines().len().to_string().len() + 2;
+        col = if col <= gutter { col * 2 } else { col + gutter };
+    }
+
+    (
+        next(top, row, inner.height as usize),
+        next(left, col, inner.width as usize),
+    )
+}
+
+/// `TextArea` only selects characters, so lines and blocks are highlighted over it.
+fn highlight_visual(
+    frame: &mut Frame,
+    inner: Rect,
+    editor: &mut TextArea,
+    (top_row, top_col): (usize, usize),
+    visual: Visual,
+) {
+    let style = editor.selection_style();
+    let gutter = match editor.line_number_style() {
+        Some(_) => editor.lines().len().to_string().len() + 2,
+        None => 0,
+    };
+    let tab_length = editor.tab_length();
+    let ((top, left), (bottom, right)) = visual.block(editor.cursor());
+
+    let bottom_row = top_row + inner.height as usize;
+    for row in top.max(top_row)..(bottom + 1).min(bottom_row) {
+        let line = &editor.lines()[row];
+        let (begin, end) = match visual.kind {
+            VisualKind::Blockwise if line.chars().count() <= left => continue,
+            VisualKind::Blockwise => (left, right + 1),
+            _ => (0, line.chars().count()),
+        };
+        // an empty line of a linewise selection still shows as selected
+        let begin = display_width(line, begin, tab_length);
+        let end = display_width(line, end, tab_length).max(begin + 1);
+
+        let y = inner.y + (row - top_row) as u16;
+        for x in (gutter + begin..gutter + end).filter(|x| *x >= top_col) {
+            let x = x - top_col;
+            if x < inner.width as usize {
+                frame.buffer_mut()[(inner.x + x as u16, y)].set_style(style);
+            }
+        }
+    }
+}
+
+/// Cells taken by the first `col` characters of the line, with tabs expanded.
+fn display_width(line: &str, col: usize, tab_length: u8) -> usize {
+    line.chars().take(col).fold(0, |width, c| match c {
+        '\t' if tab_length > 0 => width + tab_length as usize - width % tab_length as usize,
+        c => width + Span::raw(c.to_string()).width(),
+    })
 }
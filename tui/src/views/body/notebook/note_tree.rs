@@ -1,12 +1,14 @@
 use {
-    crate::context::{
-        notebook::{ContextState, TreeItem},
-        NotebookContext,
+    crate::{
+        context::{
+            notebook::{ContextState, TreeItem},
+            NotebookContext,
+        },
+        theme::Theme,
     },
     ratatui::{
         layout::Rect,
-        style::{Color, Style, Stylize},
-        text::Line,
+        text::{Line, Span},
         widgets::{Block, HighlightSpacing, List, ListDirection},
         Frame,
     },
@@ -15,18 +17,18 @@ use {
 const CLOSED_SYMBOL: &str = "▶ ";
 const OPEN_SYMBOL: &str = "▼ ";
 
-pub fn draw(frame: &mut Frame, area: Rect, context: &mut NotebookContext) {
+pub fn draw(frame: &mut Frame, area: Rect, context: &mut NotebookContext, theme: &Theme) {
     let note_tree_focused = matches!(
         context.state,
         ContextState::NoteTreeBrowsing | ContextState::NoteTreeNumbering
     );
     let title = "[Browser]";
     let title = if note_tree_focused {
-        title.light_blue()
+        Span::styled(title, theme.browser_title)
     } else {
-        title.dark_gray()
+        Span::styled(title, theme.hint)
     };
-    let block = Block::bordered().title(title);
+    let block = Block::bordered().border_style(theme.border).title(title);
     let inner_area = block.inner(area);
 
     let tree_items = context.tree_items.iter().map(|item| match item {
@@ -46,11 +48,11 @@ pub fn draw(frame: &mut Frame, area: Rect, context: &mut NotebookContext) {
     });
 
     let list = List::new(tree_items)
-        .highlight_style(Style::new().fg(Color::White).bg(if note_tree_focused {
-            Color::Blue
+        .highlight_style(if note_tree_focused {
+            theme.selection
         } else {
-            Color::DarkGray
-        }))
+            theme.selection_unfocused
+        })
         .highlight_symbol(" ")
         .highlight_spacing(HighlightSpacing::Always)
         .direction(ListDirection::TopToBottom);
@@ -0,0 +1,20 @@
+use {
+    crate::{context::notebook::CommandLine, theme::Theme},
+    ratatui::{
+        layout::{Constraint::Length, Constraint::Min, Layout, Rect},
+        style::Style,
+        text::Text,
+        Frame,
+    },
+};
+
+pub fn draw(frame: &mut Frame, area: Rect, command_line: &mut CommandLine, theme: &Theme) {
+    let [prefix_area, input_area] = Layout::horizontal([Length(1), Min(0)]).areas(area);
+
+    command_line.widget.set_style(Style::default());
+    command_line.widget.set_cursor_style(theme.cursor);
+    command_line.widget.set_cursor_line_style(Style::default());
+
+    frame.render_widget(Text::raw(command_line.prefix.to_string()), prefix_area);
+    frame.render_widget(&command_line.widget, input_area);
+}
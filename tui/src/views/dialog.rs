@@ -3,8 +3,11 @@ mod confirm;
 mod directory_actions;
 mod editor_keymap;
 mod help;
+mod log_viewer;
 mod note_actions;
 mod prompt;
+mod registers;
+mod swaps;
 mod vim_keymap;
 
 use {
@@ -16,14 +19,23 @@ use {
 };
 
 pub fn draw(frame: &mut Frame, context: &mut Context) {
-    if let Some(kind) = context.vim_keymap {
-        vim_keymap::draw(frame, kind);
+    if context.log_viewer.is_some() {
+        log_viewer::draw(frame, context);
+        return;
+    } else if context.swaps.is_some() {
+        swaps::draw(frame, context);
+        return;
+    } else if let Some(kind) = context.vim_keymap {
+        vim_keymap::draw(frame, kind, &context.settings);
+        return;
+    } else if context.register_viewer {
+        registers::draw(frame, context);
         return;
     } else if context.editor_keymap {
-        editor_keymap::draw(frame);
+        editor_keymap::draw(frame, &context.settings.theme);
         return;
     } else if context.help {
-        help::draw(frame);
+        help::draw(frame, &context.settings.theme);
         return;
     } else if context.alert.is_some() {
         alert::draw(frame, context);
@@ -38,10 +50,10 @@ pub fn draw(frame: &mut Frame, context: &mut Context) {
 
     match context.notebook.state {
         context::notebook::ContextState::NoteActionsDialog => {
-            note_actions::draw(frame, &mut context.notebook);
+            note_actions::draw(frame, &mut context.notebook, &context.settings.theme);
         }
         context::notebook::ContextState::DirectoryActionsDialog => {
-            directory_actions::draw(frame, &mut context.notebook);
+            directory_actions::draw(frame, &mut context.notebook, &context.settings.theme);
         }
         _ => {}
     }
@@ -2,7 +2,7 @@ use {
     crate::{context::Context, logger::*},
     ratatui::{
         layout::{Alignment, Constraint::Length, Flex, Layout},
-        style::{Style, Stylize},
+        style::Style,
         text::Line,
         widgets::{Block, Clear, Padding, Paragraph, Wrap},
         Frame,
@@ -10,12 +10,14 @@ use {
 };
 
 pub fn draw(frame: &mut Frame, context: &mut Context) {
+    let theme = &context.settings.theme;
     let [area] = Layout::horizontal([Length(45)])
         .flex(Flex::Center)
         .areas(frame.area());
     let [area] = Layout::vertical([Length(8)]).flex(Flex::Center).areas(area);
 
     let block = Block::bordered()
+        .border_style(theme.dialog_border)
         .padding(Padding::new(2, 2, 1, 1))
         .title("Alert")
         .title_alignment(Alignment::Center);
@@ -35,7 +37,7 @@ pub fn draw(frame: &mut Frame, context: &mut Context) {
         .wrap(Wrap { trim: true })
         .style(Style::default())
         .alignment(Alignment::Left);
-    let control = Line::from("Press any key to close".dark_gray()).centered();
+    let control = Line::styled("Press any key to close ([Ctrl+l] logs)", theme.hint).centered();
 
     frame.render_widget(Clear, area);
     frame.render_widget(block, area);
@@ -2,19 +2,22 @@ use {
     crate::{context::Context, logger::*},
     ratatui::{
         layout::{Alignment, Constraint::Length, Flex, Layout},
-        style::{Style, Stylize},
+        style::Style,
+        text::Line,
         widgets::{Block, Clear, Padding, Paragraph, Wrap},
         Frame,
     },
 };
 
 pub fn draw(frame: &mut Frame, context: &mut Context) {
+    let theme = &context.settings.theme;
     let [area] = Layout::horizontal([Length(40)])
         .flex(Flex::Center)
         .areas(frame.area());
     let [area] = Layout::vertical([Length(9)]).flex(Flex::Center).areas(area);
 
     let block = Block::bordered()
+        .border_style(theme.dialog_border)
         .padding(Padding::new(2, 2, 1, 1))
         .title("Confirm")
         .title_alignment(Alignment::Center);
@@ -33,8 +36,8 @@ pub fn draw(frame: &mut Frame, context: &mut Context) {
         .alignment(Alignment::Left);
 
     let lines = vec![
-        "[y] Confirm".dark_gray().into(),
-        "[n] Cancel".dark_gray().into(),
+        Line::styled("[y] Confirm", theme.hint),
+        Line::styled("[n] Cancel", theme.hint),
     ];
     let control = Paragraph::new(lines)
         .wrap(Wrap { trim: true })
@@ -1,26 +1,30 @@
-use ratatui::{
-    layout::{Alignment, Constraint::Length, Flex, Layout},
-    style::{Style, Stylize},
-    text::Line,
-    widgets::{Block, Clear, Padding, Paragraph, Wrap},
-    Frame,
+use {
+    crate::theme::Theme,
+    ratatui::{
+        layout::{Alignment, Constraint::Length, Flex, Layout},
+        style::Style,
+        text::Line,
+        widgets::{Block, Clear, Padding, Paragraph, Wrap},
+        Frame,
+    },
 };
 
-pub fn draw(frame: &mut Frame) {
+pub fn draw(frame: &mut Frame, theme: &Theme) {
     let [area] = Layout::horizontal([Length(100)])
         .flex(Flex::Center)
         .areas(frame.area());
-    let [area] = Layout::vertical([Length(38)])
+    let [area] = Layout::vertical([Length(40)])
         .flex(Flex::Center)
         .areas(area);
 
     let block = Block::bordered()
+        .border_style(theme.dialog_border)
         .padding(Padding::new(2, 2, 1, 1))
         .title("Editor Keymap")
         .title_alignment(Alignment::Center);
 
     let inner_area = block.inner(area);
-    let [message_area, control_area] = Layout::vertical([Length(33), Length(1)])
+    let [message_area, control_area] = Layout::vertical([Length(35), Length(1)])
         .flex(Flex::SpaceBetween)
         .areas(inner_area);
 
@@ -53,6 +57,8 @@ pub fn draw(frame: &mut Frame) {
 | `Alt+>`, `Ctrl+Alt+N`, `Ctrl+Alt+↓`           | Move cursor to bottom of lines             |
 | `Ctrl+V`, `PageDown`                          | Scroll down by page                        |
 | `Alt+V`, `PageUp`                             | Scroll up by page                          |
+| `Ctrl+S`                                      | Save note                                  |
+| `Alt+S`                                       | Save all notes                             |
 
 Thanks to tui-textarea
 "
@@ -64,7 +70,7 @@ Thanks to tui-textarea
         .wrap(Wrap { trim: true })
         .style(Style::default())
         .alignment(Alignment::Left);
-    let control = Line::from("Press any key to close".dark_gray()).centered();
+    let control = Line::styled("Press any key to close", theme.hint).centered();
 
     frame.render_widget(Clear, area);
     frame.render_widget(block, area);
@@ -0,0 +1,101 @@
+use {
+    crate::{context::Context, logger::*},
+    log::Level,
+    ratatui::{
+        layout::{Alignment, Constraint::Length, Flex, Layout},
+        text::{Line, Span},
+        widgets::{Block, Clear, Padding, Paragraph, Wrap},
+        Frame,
+    },
+};
+
+pub fn draw(frame: &mut Frame, context: &mut Context) {
+    let theme = &context.settings.theme;
+    let log_viewer = context
+        .log_viewer
+        .as_mut()
+        .log_expect("log viewer not found");
+
+    let [area] = Layout::horizontal([Length(120)])
+        .flex(Flex::Center)
+        .areas(frame.area());
+    let [area] = Layout::vertical([Length(frame.area().height.saturating_sub(4))])
+        .flex(Flex::Center)
+        .areas(area);
+
+    let title = if log_viewer.errors_only {
+        "Logs - warnings and errors"
+    } else {
+        "Logs"
+    };
+    let block = Block::bordered()
+        .border_style(theme.dialog_border)
+        .padding(Padding::new(2, 2, 1, 1))
+        .title(title)
+        .title_alignment(Alignment::Center);
+
+    let inner_area = block.inner(area);
+    let [message_area, control_area] =
+        Layout::vertical([Length(inner_area.height.saturating_sub(2)), Length(1)])
+            .flex(Flex::SpaceBetween)
+            .areas(inner_area);
+
+    let mut lines = Vec::new();
+    for (n, entries) in log_viewer.sessions.iter().enumerate() {
+        let entries = entries
+            .iter()
+            .rev()
+            .filter(|entry| log_viewer.is_visible(entry))
+            .collect::<Vec<_>>();
+        if entries.is_empty() {
+            continue;
+        }
+
+        let heading = match n {
+            0 => "Current session".to_owned(),
+            1 => "Previous session".to_owned(),
+            n => format!("{n} sessions ago"),
+        };
+        if !lines.is_empty() {
+            lines.push(Line::raw(""));
+        }
+        lines.push(Line::styled(heading, theme.heading));
+
+        for entry in entries {
+            let style = match entry.level {
+                Level::Error => theme.error,
+                Level::Warn => theme.warning,
+                _ => theme.hint,
+            };
+            let mut messages = entry.message.lines();
+
+            lines.push(Line::from(vec![
+                Span::styled(format!("{} ", entry.timestamp), theme.hint),
+                Span::styled(format!("{:<5} ", entry.level), style),
+                Span::raw(messages.next().unwrap_or_default().to_owned()),
+            ]));
+            lines.extend(messages.map(|message| Line::raw(format!("    {message}"))));
+        }
+    }
+
+    if lines.is_empty() {
+        lines.push(Line::styled("No logs yet", theme.hint));
+    }
+
+    log_viewer.scroll = log_viewer
+        .scroll
+        .min(lines.len().saturating_sub(1).try_into().unwrap_or(u16::MAX));
+    let paragraph = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .scroll((log_viewer.scroll, 0));
+    let control = Line::styled(
+        "[j|k] Scroll | [Ctrl+d|Ctrl+u] Page | [g|G] Top | Bottom | [e] Errors only | [r] Reload | [Esc] Close",
+        theme.hint,
+    )
+    .centered();
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+    frame.render_widget(paragraph, message_area);
+    frame.render_widget(control, control_area);
+}
@@ -5,19 +5,22 @@ use {
     },
     ratatui::{
         layout::{Alignment, Constraint::Length, Flex, Layout},
-        style::{Style, Stylize},
+        style::Style,
+        text::Line,
         widgets::{Block, Clear, Padding, Paragraph, Wrap},
         Frame,
     },
 };
 
 pub fn draw(frame: &mut Frame, context: &mut Context) {
+    let theme = &context.settings.theme;
     let ContextPrompt {
         message, widget, ..
     } = context
         .prompt
-        .as_ref()
+        .as_mut()
         .log_expect("prompt message not found");
+    widget.set_cursor_style(theme.cursor);
     let num_lines = message.len() as u16;
 
     let [area] = Layout::horizontal([Length(61)])
@@ -28,6 +31,7 @@ pub fn draw(frame: &mut Frame, context: &mut Context) {
         .areas(area);
 
     let block = Block::bordered()
+        .border_style(theme.dialog_border)
         .padding(Padding::new(2, 2, 1, 1))
         .title("Prompt")
         .title_alignment(Alignment::Center);
@@ -40,8 +44,8 @@ pub fn draw(frame: &mut Frame, context: &mut Context) {
         .alignment(Alignment::Left);
 
     let lines = vec![
-        "[Enter] Submit".dark_gray().into(),
-        "[Esc] Cancel".dark_gray().into(),
+        Line::styled("[Enter] Submit", theme.hint),
+        Line::styled("[Esc] Cancel", theme.hint),
     ];
     let control = Paragraph::new(lines)
         .wrap(Wrap { trim: true })
@@ -51,6 +55,6 @@ pub fn draw(frame: &mut Frame, context: &mut Context) {
     frame.render_widget(Clear, area);
     frame.render_widget(block, area);
     frame.render_widget(message, message_area);
-    frame.render_widget(widget, input_area);
+    frame.render_widget(&*widget, input_area);
     frame.render_widget(control, control_area);
 }
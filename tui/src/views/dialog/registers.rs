@@ -0,0 +1,63 @@
+use {
+    crate::context::Context,
+    ratatui::{
+        layout::{Alignment, Constraint::Length, Flex, Layout},
+        text::{Line, Span},
+        widgets::{Block, Clear, Padding, Paragraph},
+        Frame,
+    },
+};
+
+const WIDTH: u16 = 80;
+
+pub fn draw(frame: &mut Frame, context: &mut Context) {
+    let theme = &context.settings.theme;
+    let registers = context.notebook.registers.list();
+
+    let mut lines = vec![Line::styled("Name  Type  Content", theme.heading)];
+    lines.extend(registers.into_iter().map(|(name, register)| {
+        let kind = if register.linewise { "l" } else { "c" };
+        // newlines are shown as ^J, as Vim does
+        let content = register
+            .text
+            .replace('\n', "^J")
+            .chars()
+            .take(WIDTH as usize - 18)
+            .collect::<String>();
+
+        Line::from(vec![
+            Span::raw(format!("  \"{name}   {kind}    ")),
+            Span::raw(content),
+        ])
+    }));
+    if lines.len() == 1 {
+        lines.push(Line::raw("  All registers are empty"));
+    }
+
+    let height = lines.len() as u16 + 6;
+    let [area] = Layout::horizontal([Length(WIDTH)])
+        .flex(Flex::Center)
+        .areas(frame.area());
+    let [area] = Layout::vertical([Length(height)])
+        .flex(Flex::Center)
+        .areas(area);
+
+    let block = Block::bordered()
+        .border_style(theme.dialog_border)
+        .padding(Padding::new(2, 2, 1, 1))
+        .title("Registers")
+        .title_alignment(Alignment::Center);
+
+    let inner_area = block.inner(area);
+    let [message_area, control_area] =
+        Layout::vertical([Length(inner_area.height.saturating_sub(2)), Length(1)])
+            .flex(Flex::SpaceBetween)
+            .areas(inner_area);
+
+    let control = Line::styled("Press any key to close", theme.hint).centered();
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+    frame.render_widget(Paragraph::new(lines), message_area);
+    frame.render_widget(control, control_area);
+}
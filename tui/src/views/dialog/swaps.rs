@@ -0,0 +1,52 @@
+use {
+    crate::{context::Context, logger::*},
+    ratatui::{
+        layout::{Alignment, Constraint::Length, Flex, Layout},
+        text::Line,
+        widgets::{Block, Clear, Padding, Paragraph},
+        Frame,
+    },
+};
+
+pub fn draw(frame: &mut Frame, context: &mut Context) {
+    let theme = &context.settings.theme;
+    let swaps = context.swaps.as_ref().log_expect("swaps not found");
+
+    let mut lines = vec![
+        Line::raw("Unsaved changes of the last session were found."),
+        Line::raw(""),
+    ];
+    lines.extend(
+        swaps
+            .iter()
+            .map(|swap| Line::raw(format!("  - {}", swap.note_name))),
+    );
+
+    let height = lines.len() as u16 + 6;
+    let [area] = Layout::horizontal([Length(56)])
+        .flex(Flex::Center)
+        .areas(frame.area());
+    let [area] = Layout::vertical([Length(height)])
+        .flex(Flex::Center)
+        .areas(area);
+
+    let block = Block::bordered()
+        .border_style(theme.dialog_border)
+        .padding(Padding::new(2, 2, 1, 1))
+        .title("Recover")
+        .title_alignment(Alignment::Center);
+
+    let inner_area = block.inner(area);
+    let [message_area, control_area] =
+        Layout::vertical([Length(inner_area.height.saturating_sub(2)), Length(1)])
+            .flex(Flex::SpaceBetween)
+            .areas(inner_area);
+
+    let control =
+        Line::styled("[r] Recover | [d] Discard | [Esc] Decide later", theme.hint).centered();
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+    frame.render_widget(Paragraph::new(lines), message_area);
+    frame.render_widget(control, control_area);
+}
@@ -1,20 +1,22 @@
 use {
+    crate::{keymap::Mode, settings::Settings},
     glues_core::transition::VimKeymapKind,
     ratatui::{
         layout::{Alignment, Constraint::Length, Flex, Layout},
-        style::{Style, Stylize},
-        text::Line,
+        style::Style,
+        text::{Line, Span},
         widgets::{Block, Clear, Padding, Paragraph, Wrap},
         Frame,
     },
 };
 
-pub fn draw(frame: &mut Frame, keymap_kind: VimKeymapKind) {
+pub fn draw(frame: &mut Frame, keymap_kind: VimKeymapKind, settings: &Settings) {
+    let Settings { keymap, theme, .. } = settings;
     let (title, message) = match keymap_kind {
         VimKeymapKind::NormalIdle => (
             "VIM NORMAL MODE KEYMAP",
             vec![
-                Line::from("TO INSERT MODE".white().on_dark_gray()),
+                Line::styled("TO INSERT MODE", theme.heading),
                 Line::raw("[i] Go to insert mode"),
                 Line::raw("[I] Go to insert mode at the beginning of the line"),
                 Line::raw("[o] Insert a new line below and go to insert mode"),
@@ -23,16 +25,19 @@ pub fn draw(frame: &mut Frame, keymap_kind: VimKeymapKind) {
                 Line::raw("[A] Move cursor to the end of the line and go to insert mode"),
                 Line::raw("[s] Delete character and go to insert mode"),
                 Line::raw("[S] Delete line and go to insert mode"),
+                Line::raw("[R] Go to replace mode, typing over the text under the cursor"),
                 Line::raw(""),
-                Line::from("TO OTHER MODES".white().on_dark_gray()),
+                Line::styled("TO OTHER MODES", theme.heading),
                 Line::raw("[c] Go to change mode (prepare to edit text)"),
                 Line::raw("[v] Go to visual mode (select text to edit or copy)"),
+                Line::raw("[V] Go to visual line mode (select whole lines)"),
+                Line::raw("[Ctrl+v] Go to visual block mode (select a column block)"),
                 Line::raw("[g] Go to gateway mode (access extended commands)"),
                 Line::raw("[y] Go to yank mode (prepare to copy text)"),
                 Line::raw("[d] Go to delete mode (prepare to delete text)"),
                 Line::raw("[1-9] Go to numbering mode (repeat or extend actions with numbers)"),
                 Line::raw(""),
-                Line::from("MOVE CURSOR".white().on_dark_gray()),
+                Line::styled("MOVE CURSOR", theme.heading),
                 Line::raw("[h] Move cursor left"),
                 Line::raw("[j] Move cursor down"),
                 Line::raw("[k] Move cursor up"),
@@ -44,27 +49,82 @@ pub fn draw(frame: &mut Frame, keymap_kind: VimKeymapKind) {
                 Line::raw("[$] Move cursor to the end of the line"),
                 Line::raw("[^] Move cursor to the first non-blank character of the line"),
                 Line::raw("[G] Move cursor to the end of the file"),
+                Line::raw("[f|F] Move cursor to the next | previous given character in the line"),
+                Line::raw("[t|T] Move cursor before the next | after the previous given character"),
+                Line::raw("[;|,] Repeat the last character find | in the other direction"),
+                Line::raw("[}|{] Move cursor to the next | previous blank line between paragraphs"),
+                Line::raw("[)|(] Move cursor to the start of the next | current or previous sentence"),
+                Line::raw("[%] Move cursor to the bracket matching the one under or after it"),
+                Line::raw("[H|M|L] Move cursor to the top | middle | bottom of the screen"),
+                Line::raw("[Ctrl+d|Ctrl+u] Scroll down | up half a screen"),
+                Line::raw("[Ctrl+f|Ctrl+b] Scroll down | up a screen"),
                 Line::raw(""),
-                Line::from("EDIT TEXT".white().on_dark_gray()),
+                Line::styled("SEARCH", theme.heading),
+                Line::raw("[/] Search forward, regex with smart-case"),
+                Line::raw("[?] Search backward"),
+                Line::raw("[n] Next match, or browse notes when no search is highlighted"),
+                Line::raw("[N] Previous match"),
+                Line::raw("[Esc] Clear the search highlight"),
+                Line::raw(""),
+                Line::styled("REGISTERS", theme.heading),
+                Line::raw("[\"a-z] Use a named register for the next yank, delete or paste"),
+                Line::raw("[\"A-Z] Append to the named register"),
+                Line::raw("[\"0] Last yank, [\"1-9] Last deletes of whole lines, [\"-] Small deletes"),
+                Line::raw("[\"+] or [\"*] System clipboard"),
+                Line::raw("[\"_] Black hole, deletes without touching any register"),
+                Line::raw(""),
+                Line::styled("MACROS", theme.heading),
+                Line::raw("[q{a-z}] Record keys into the register, [q] again to stop"),
+                Line::raw("[q{A-Z}] Append to the recorded keys"),
+                Line::raw("[@{a-z}] Run the recorded keys, [@@] Run the last macro again"),
+                Line::raw(""),
+                Line::styled("MARKS AND JUMPS", theme.heading),
+                Line::raw("[m{a-z}] Mark the cursor in this note"),
+                Line::raw("[m{A-Z}] Mark the cursor across notes, jumping there reopens the note"),
+                Line::raw("[`{mark}] Jump to the mark, ['{mark}] Jump to the line of the mark"),
+                Line::raw("[Ctrl+o|Ctrl+i] Go to the older | newer position in the jump list"),
+                Line::raw("Jumps are searches, marks, [G], [gg], [%], [(|)|{|}], [H|M|L] and :<line>"),
+                Line::raw(""),
+                Line::styled("EX COMMANDS", theme.heading),
+                Line::raw("[:w] Save the note, [:wa] Save all notes"),
+                Line::raw("[:q] Close the tab, [:q!] dropping its changes, [:wq|:x] saving them"),
+                Line::raw("[:e dir/note] Open a note by its path"),
+                Line::raw("[:<line>] Move to a line, e.g. :12, :$ or :.+3"),
+                Line::raw("[:noh] Clear the search highlight"),
+                Line::raw("[:reg] Show the registers"),
+                Line::raw("[:s/pat/rep/g] Substitute, with ranges like % or 2,$"),
+                Line::raw(""),
+                Line::styled("EDIT TEXT", theme.heading),
                 Line::raw("[x] Delete character under the cursor"),
+                Line::raw("[r] Replace the character under the cursor with the next one typed"),
+                Line::raw("[~] Toggle the case of the character under the cursor"),
+                Line::raw("[J] Join the next line to the cursor line"),
+                Line::raw("[>>|<<] Indent | Outdent the line by editor.indent_width spaces"),
+                Line::raw("[g~|gu|gU] Toggle | Lower | Upper the case of a motion or text object"),
+                Line::raw("[g~~|guu|gUU] Toggle | Lower | Upper the case of the line"),
+                Line::raw("[.] Repeat the last change, including the text typed in insert mode"),
                 Line::raw("[u] Undo the last change"),
                 Line::raw("[Ctrl+r] Redo the last undone change"),
+                Line::raw(""),
+                Line::styled("SAVE", theme.heading),
+                Line::raw("[Ctrl+s] Save the note"),
+                Line::raw("[Alt+s] Save all notes"),
             ],
         ),
         VimKeymapKind::NormalNumbering => ("VIM NORMAL MODE KEYMAP - NUMBERING", vec![
-            Line::from("EXTENDING NUMBERING MODE".white().on_dark_gray()),
+            Line::styled("EXTENDING NUMBERING MODE", theme.heading),
             Line::raw("[0-9] Append additional digits to extend the current command"),
             Line::raw(""),
-            Line::from("TO INSERT MODE".white().on_dark_gray()),
+            Line::styled("TO INSERT MODE", theme.heading),
             Line::raw("[s] Delete specified number of characters and go to insert mode"),
             Line::raw("[S] Delete the entire line and go to insert mode"),
             Line::raw(""),
-            Line::from("TO OTHER MODES".white().on_dark_gray()),
+            Line::styled("TO OTHER MODES", theme.heading),
             Line::raw("[c] Go to change mode with repeat count (prepare to edit text)"),
             Line::raw("[y] Go to yank mode with repeat count (prepare to copy text)"),
             Line::raw("[d] Go to delete mode with repeat count (prepare to delete text)"),
             Line::raw(""),
-            Line::from("MOVE CURSOR AND RETURN TO NORMAL MODE".white().on_dark_gray()),
+            Line::styled("MOVE CURSOR AND RETURN TO NORMAL MODE", theme.heading),
             Line::raw("[h] Move cursor left by the specified number of times"),
             Line::raw("[j] Move cursor down by the specified number of times"),
             Line::raw("[k] Move cursor up by the specified number of times"),
@@ -73,68 +133,100 @@ pub fn draw(frame: &mut Frame, keymap_kind: VimKeymapKind) {
             Line::raw("[e] Move cursor to the end of the next word, repeated by the specified number"),
             Line::raw("[b] Move cursor to the start of the previous word, repeated by the specified number"),
             Line::raw("[G] Move cursor to the specified line number"),
+            Line::raw("[f|F|t|T] Find the given character the specified number of times"),
+            Line::raw("[;|,] Repeat the last character find the specified number of times"),
+            Line::raw("[}|{|)|(] Move over the specified number of paragraphs | sentences"),
+            Line::raw("[%] Move cursor to the line that percent of the way through the note"),
+            Line::raw("[H|L] Move cursor to the specified line from the top | bottom of the screen"),
+            Line::raw("[Ctrl+d|Ctrl+u|Ctrl+f|Ctrl+b] Scroll the specified number of times"),
+            Line::raw("[Ctrl+o|Ctrl+i] Go the specified number of positions back | forward in the jump list"),
             Line::raw(""),
-            Line::from("EDIT TEXT AND RETURN TO NORMAL MODE".white().on_dark_gray()),
+            Line::styled("EDIT TEXT AND RETURN TO NORMAL MODE", theme.heading),
             Line::raw("[x] Delete specified number of characters and return to normal mode"),
+            Line::raw("[r] Replace the specified number of characters with the next one typed"),
+            Line::raw("[~] Toggle the case of the specified number of characters"),
+            Line::raw("[J] Join the specified number of lines"),
+            Line::raw("[>>|<<] Indent | Outdent the specified number of lines"),
+            Line::raw("[gUU] and the like change the case of the specified number of lines"),
+            Line::raw("[.] Repeat the last change with the specified number in place of its own"),
+            Line::raw("[@] Run a macro the specified number of times"),
         ]),
         VimKeymapKind::NormalDelete => ("VIM NORMAL MODE KEYMAP - DELETE", vec![
-            Line::from("TO NUMBERING MODE".white().on_dark_gray()),
+            Line::styled("TO NUMBERING MODE", theme.heading),
             Line::raw("[1-9] Go to delete numbering mode"),
             Line::raw(""),
-            Line::from("TO DELETE INSIDE MODE".white().on_dark_gray()),
-            Line::raw("[i] Go to delete inside mode"),
+            Line::styled("TEXT OBJECTS", theme.heading),
+            Line::raw("[i|a] Delete inside | around the object typed next, e.g. di( or daw"),
+            Line::raw("Objects: [w|W] word, [s] sentence, [p] paragraph, [\"|'|`] quotes, [(|[|{|<] brackets"),
             Line::raw(""),
-            Line::from("DELETE TEXT".white().on_dark_gray()),
+            Line::styled("DELETE TEXT", theme.heading),
             Line::raw("[d] Delete the specified number of lines"),
             Line::raw("[e] Delete the word from the cursor to the end of the current word."),
             Line::raw("[b] Delete the word before the cursor."),
             Line::raw("[0] Delete to the beginning of the line"),
             Line::raw("[$] Delete to the end of the line, repeated by the specified number"),
+            Line::raw("[f|t] Delete through | up to the given character, e.g. dt)"),
+            Line::raw("[F|T] Delete back to the given character"),
+            Line::raw("[;|,] Delete to the last character find"),
+            Line::raw("[}|{|)|(] Delete to the end | start of the paragraph | sentence"),
+            Line::raw("[%] Delete through the matching bracket"),
+            Line::raw("[H|M|L] Delete the lines up to the top | middle | bottom of the screen"),
         ]),
         VimKeymapKind::NormalDelete2 => ("VIM NORMAL MODE KEYMAP - DELETE NUMBERING", vec![
-            Line::from("EXTENDING NUMBERING MODE".white().on_dark_gray()),
+            Line::styled("EXTENDING NUMBERING MODE", theme.heading),
             Line::raw("[0-9] Append additional digits to extend the current command"),
             Line::raw(""),
-            Line::from("TO DELETE INSIDE MODE".white().on_dark_gray()),
-            Line::raw("[i] Go to delete inside mode"),
+            Line::styled("TEXT OBJECTS", theme.heading),
+            Line::raw("[i|a] Delete inside | around the object typed next, e.g. di( or daw"),
+            Line::raw("Objects: [w|W] word, [s] sentence, [p] paragraph, [\"|'|`] quotes, [(|[|{|<] brackets"),
             Line::raw(""),
-            Line::from("DELETE TEXT".white().on_dark_gray()),
+            Line::styled("DELETE TEXT", theme.heading),
             Line::raw("[d] Delete the specified number of lines"),
+            Line::raw("[f|t|F|T] Delete to the given character, found the specified number of times"),
+            Line::raw("[}|{|)|(] Delete over the specified number of paragraphs | sentences"),
+            Line::raw("[%] Delete the lines up to that percent of the way through the note"),
         ]),
         VimKeymapKind::NormalChange => ("VIM NORMAL MODE KEYMAP - CHANGE", vec![
-            Line::from("TO CHANGE INSIDE MODE".white().on_dark_gray()),
-            Line::raw("[i] Go to change inside mode"),
+            Line::styled("TEXT OBJECTS", theme.heading),
+            Line::raw("[i|a] Change inside | around the object typed next, e.g. ci( or caw"),
+            Line::raw("Objects: [w|W] word, [s] sentence, [p] paragraph, [\"|'|`] quotes, [(|[|{|<] brackets"),
             Line::raw(""),
-            Line::from("CHANGE TEXT AND GO TO INSERT MODE".white().on_dark_gray()),
+            Line::styled("CHANGE TEXT AND GO TO INSERT MODE", theme.heading),
             Line::raw("[c] Delete the specified number of lines"),
             Line::from(vec![
                 "[e] ".into(),
-                "or ".dark_gray(),
+                Span::styled("or ", theme.hint),
                 "[w] Delete to the end of the word by the specified number of times".into(),
             ]),
             Line::raw("[b] Delete to the start of the previous word, repeated by the specified number"),
             Line::raw("[0] Delete to the beginning of the line"),
             Line::raw("[$] Delete to the end of the line, repeated by the specified number"),
+            Line::raw("[f|t|F|T] Delete to the given character, e.g. cf,"),
+            Line::raw("[;|,] Delete to the last character find"),
+            Line::raw("[}|{|)|(|%] Delete to the paragraph, sentence or bracket motion"),
         ]),
         VimKeymapKind::NormalChange2 => ("VIM NORMAL MODE KEYMAP - CHANGE NUMBERING", vec![
-            Line::from("EXTENDING NUMBERING MODE".white().on_dark_gray()),
+            Line::styled("EXTENDING NUMBERING MODE", theme.heading),
             Line::raw("[0-9] Append additional digits to extend the current command"),
             Line::raw(""),
-            Line::from("TO CHANGE INSIDE MODE".white().on_dark_gray()),
-            Line::raw("[i] Go to change inside mode"),
+            Line::styled("TEXT OBJECTS", theme.heading),
+            Line::raw("[i|a] Change inside | around the object typed next, e.g. ci( or caw"),
+            Line::raw("Objects: [w|W] word, [s] sentence, [p] paragraph, [\"|'|`] quotes, [(|[|{|<] brackets"),
             Line::raw(""),
-            Line::from("CHANGE TEXT AND GO TO INSERT MODE".white().on_dark_gray()),
+            Line::styled("CHANGE TEXT AND GO TO INSERT MODE", theme.heading),
             Line::raw("[c] Delete the specified number of lines"),
             Line::from(vec![
                 "[e] ".into(),
-                "or ".dark_gray(),
+                Span::styled("or ", theme.hint),
                 "[w] Delete to the end of the word by the specified number of times".into(),
             ]),
             Line::raw("[b] Delete to the start of the previous word, repeated by the specified number"),
             Line::raw("[$] Delete to the end of the line, repeated by the specified number"),
+            Line::raw("[f|t|F|T] Delete to the given character, found the specified number of times"),
+            Line::raw("[}|{|)|(] Delete over the specified number of paragraphs | sentences"),
         ]),
         VimKeymapKind::VisualIdle => ("VIM VISUAL MODE KEYMAP", vec![
-            Line::from("MOVE CURSOR".white().on_dark_gray()),
+            Line::styled("MOVE CURSOR", theme.heading),
             Line::raw("[h] Move cursor left"),
             Line::raw("[j] Move cursor down"),
             Line::raw("[k] Move cursor up"),
@@ -146,31 +238,45 @@ pub fn draw(frame: &mut Frame, keymap_kind: VimKeymapKind) {
             Line::raw("[$] Move cursor to the end of the line"),
             Line::raw("[^] Move cursor to the first non-blank character of the line"),
             Line::raw("[G] Move cursor to the end of the file"),
+            Line::raw("[f|F|t|T] Move cursor to the given character in the line"),
+            Line::raw("[;|,] Repeat the last character find | in the other direction"),
+            Line::raw("[}|{] Move cursor to the next | previous blank line between paragraphs"),
+            Line::raw("[)|(] Move cursor to the start of the next | current or previous sentence"),
+            Line::raw("[%] Move cursor to the bracket matching the one under or after it"),
+            Line::raw("[H|M|L] Move cursor to the top | middle | bottom of the screen"),
+            Line::raw("[Ctrl+d|Ctrl+u] Scroll down | up half a screen"),
+            Line::raw("[Ctrl+f|Ctrl+b] Scroll down | up a screen"),
+            Line::raw("[i|a] Select inside | around a text object, e.g. vi\" or vap"),
             Line::raw(""),
-            Line::from("TO INSERT MODE".white().on_dark_gray()),
+            Line::styled("TO INSERT MODE", theme.heading),
             Line::from(vec![
                 "[s] ".into(),
-                "or ".dark_gray(),
-                "[S] Substitute selected text and go to insert mode".into(),
+                Span::styled("or ", theme.hint),
+                "[S] ".into(),
+                Span::styled("or ", theme.hint),
+                "[c] Substitute selected text and go to insert mode".into(),
             ]),
+            Line::raw("[I|A] Insert | Append on every line of the block (block mode)"),
             Line::raw(""),
-            Line::from("TO EXTENDED MODES".white().on_dark_gray()),
+            Line::styled("TO EXTENDED MODES", theme.heading),
             Line::raw("[g] Go to gateway mode for additional commands"),
             Line::raw("[1-9] Specify repeat count for subsequent actions"),
+            Line::raw("[v|V|Ctrl+v] Select characters | lines | a block, or leave the current one"),
             Line::raw(""),
-            Line::from("EDIT TEXT AND RETURN TO NORMAL MODE".white().on_dark_gray()),
+            Line::styled("EDIT TEXT AND RETURN TO NORMAL MODE", theme.heading),
             Line::from(vec![
                 "[d] ".into(),
-                "or ".dark_gray(),
+                Span::styled("or ", theme.hint),
                 "[x] Delete selected text".into(),
             ]),
             Line::raw("[y] Yank (copy) selected text"),
+            Line::raw("[>|<] Indent | Outdent the selected lines, [3>] three times as far"),
         ]),
         VimKeymapKind::VisualNumbering => ("VIM VISUAL MODE KEYMAP - NUMBERING", vec![
-            Line::from("EXTENDING NUMBERING MODE".white().on_dark_gray()),
+            Line::styled("EXTENDING NUMBERING MODE", theme.heading),
             Line::raw("[0-9] Append additional digits to extend the current command"),
             Line::raw(""),
-            Line::from("MOVE CURSOR".white().on_dark_gray()),
+            Line::styled("MOVE CURSOR", theme.heading),
             Line::raw("[h] Move cursor left by the specified number of times"),
             Line::raw("[j] Move cursor down by the specified number of times"),
             Line::raw("[k] Move cursor up by the specified number of times"),
@@ -179,6 +285,10 @@ pub fn draw(frame: &mut Frame, keymap_kind: VimKeymapKind) {
             Line::raw("[e] Move cursor to the end of the next word, repeated by the specified number"),
             Line::raw("[b] Move cursor to the start of the previous word, repeated by the specified number"),
             Line::raw("[G] Move cursor to the specified line number"),
+            Line::raw("[f|F|t|T] Find the given character the specified number of times"),
+            Line::raw("[}|{|)|(] Move over the specified number of paragraphs | sentences"),
+            Line::raw("[%] Move cursor to the line that percent of the way through the note"),
+            Line::raw("[Ctrl+d|Ctrl+u|Ctrl+f|Ctrl+b] Scroll the specified number of times"),
         ]),
     };
     let height = message.len() as u16 + 7;
@@ -191,19 +301,28 @@ pub fn draw(frame: &mut Frame, keymap_kind: VimKeymapKind) {
         .areas(area);
 
     let block = Block::bordered()
+        .border_style(theme.dialog_border)
         .padding(Padding::new(2, 2, 1, 1))
-        .title(title.white().on_dark_gray())
+        .title(Span::styled(title, theme.heading))
         .title_alignment(Alignment::Center);
 
     let inner_area = block.inner(area);
     let [message_area, control_area] = Layout::vertical([Length(height - 5), Length(1)])
         .flex(Flex::SpaceBetween)
         .areas(inner_area);
+    let mode = match keymap_kind {
+        VimKeymapKind::VisualIdle | VimKeymapKind::VisualNumbering => Mode::Visual,
+        _ => Mode::Normal,
+    };
+    let message = message
+        .into_iter()
+        .map(|line| keymap.relabel_line(mode, line))
+        .collect::<Vec<_>>();
     let paragraph = Paragraph::new(message)
         .wrap(Wrap { trim: true })
         .style(Style::default())
         .alignment(Alignment::Left);
-    let control = Line::from("Press any key to close".dark_gray()).centered();
+    let control = Line::styled("Press any key to close", theme.hint).centered();
 
     frame.render_widget(Clear, area);
     frame.render_widget(block, area);
@@ -1,22 +1,32 @@
 use {
+    crate::context::Context,
     glues_core::state::State,
-    ratatui::{layout::Rect, style::Stylize, text::Line, Frame},
+    ratatui::{
+        layout::Rect,
+        text::{Line, Span},
+        Frame,
+    },
 };
 
-pub fn draw(frame: &mut Frame, area: Rect, state: &State) {
+pub fn draw(frame: &mut Frame, area: Rect, state: &State, context: &Context) {
+    let mode = context.keymap_mode();
+    let theme = &context.settings.theme;
     let state_shortcuts = state.shortcuts();
     let num_shortcuts = state_shortcuts.len();
     let mut shortcuts = vec![" ".into()];
 
     for (i, shortcut) in state_shortcuts.iter().enumerate() {
-        shortcuts.push(shortcut.clone().black());
+        shortcuts.push(Span::styled(
+            context.settings.keymap.relabel(mode, shortcut),
+            theme.keymap,
+        ));
 
         if i < num_shortcuts - 1 {
-            shortcuts.push(" | ".light_blue().on_gray());
+            shortcuts.push(Span::styled(" | ", theme.keymap_separator));
         }
     }
 
-    let shortcuts: Line = shortcuts.into();
+    let shortcuts = Line::from(shortcuts).style(theme.keymap);
 
-    frame.render_widget(shortcuts.on_gray(), area);
+    frame.render_widget(shortcuts, area);
 }
@@ -1,11 +1,14 @@
 use {
-    crate::logger::*,
+    crate::{logger::*, theme::Theme},
     glues_core::state::State,
-    ratatui::{layout::Rect, style::Stylize, text::Text, Frame},
+    ratatui::{layout::Rect, text::Text, Frame},
 };
 
-pub fn draw(frame: &mut Frame, area: Rect, state: &State) {
+pub fn draw(frame: &mut Frame, area: Rect, state: &State, theme: &Theme) {
     let description = state.describe().log_unwrap() + " ";
 
-    frame.render_widget(Text::raw(description).centered().black().on_gray(), area);
+    frame.render_widget(
+        Text::raw(description).centered().style(theme.statusbar),
+        area,
+    );
 }